@@ -0,0 +1,1681 @@
+// NOTE: Written by hand after the layout of the flatc 24.12.23 Rust output for
+// commands.fbs, the compiler wasn't available. Replace it with the output of
+// `scripts/regenerate_schemes.sh`, the CI check fails until then.
+
+use core::mem;
+use core::cmp::Ordering;
+
+extern crate flatbuffers;
+use self::flatbuffers::{EndianScalar, Follow};
+
+#[allow(unused_imports, dead_code)]
+pub mod tech_paws {
+
+  use core::mem;
+  use core::cmp::Ordering;
+
+  extern crate flatbuffers;
+  use self::flatbuffers::{EndianScalar, Follow};
+#[allow(unused_imports, dead_code)]
+pub mod schemes {
+
+  use core::mem;
+  use core::cmp::Ordering;
+
+  extern crate flatbuffers;
+  use self::flatbuffers::{EndianScalar, Follow};
+
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+pub const ENUM_MIN_RENDER_COMMAND_TYPE: i32 = 0;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+pub const ENUM_MAX_RENDER_COMMAND_TYPE: i32 = 11;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+#[allow(non_camel_case_types)]
+pub const ENUM_VALUES_RENDER_COMMAND_TYPE: [RenderCommandType; 12] = [
+  RenderCommandType::PushColor,
+  RenderCommandType::PushVec2f,
+  RenderCommandType::SetColorUniform,
+  RenderCommandType::PushColorShader,
+  RenderCommandType::DrawLines,
+  RenderCommandType::DrawPoints,
+  RenderCommandType::DrawQuads,
+  RenderCommandType::DrawText,
+  RenderCommandType::PushString,
+  RenderCommandType::PushTextShader,
+  RenderCommandType::SetCamera,
+  RenderCommandType::PushInt32,
+];
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[repr(transparent)]
+pub struct RenderCommandType(pub i32);
+#[allow(non_upper_case_globals)]
+impl RenderCommandType {
+  pub const PushColor: Self = Self(0);
+  pub const PushVec2f: Self = Self(1);
+  pub const SetColorUniform: Self = Self(2);
+  pub const PushColorShader: Self = Self(3);
+  pub const DrawLines: Self = Self(4);
+  pub const DrawPoints: Self = Self(5);
+  pub const DrawQuads: Self = Self(6);
+  pub const DrawText: Self = Self(7);
+  pub const PushString: Self = Self(8);
+  pub const PushTextShader: Self = Self(9);
+  pub const SetCamera: Self = Self(10);
+  pub const PushInt32: Self = Self(11);
+
+  pub const ENUM_MIN: i32 = 0;
+  pub const ENUM_MAX: i32 = 11;
+  pub const ENUM_VALUES: &'static [Self] = &[
+    Self::PushColor,
+    Self::PushVec2f,
+    Self::SetColorUniform,
+    Self::PushColorShader,
+    Self::DrawLines,
+    Self::DrawPoints,
+    Self::DrawQuads,
+    Self::DrawText,
+    Self::PushString,
+    Self::PushTextShader,
+    Self::SetCamera,
+    Self::PushInt32,
+  ];
+  /// Returns the variant's name or "" if unknown.
+  pub fn variant_name(self) -> Option<&'static str> {
+    match self {
+      Self::PushColor => Some("PushColor"),
+      Self::PushVec2f => Some("PushVec2f"),
+      Self::SetColorUniform => Some("SetColorUniform"),
+      Self::PushColorShader => Some("PushColorShader"),
+      Self::DrawLines => Some("DrawLines"),
+      Self::DrawPoints => Some("DrawPoints"),
+      Self::DrawQuads => Some("DrawQuads"),
+      Self::DrawText => Some("DrawText"),
+      Self::PushString => Some("PushString"),
+      Self::PushTextShader => Some("PushTextShader"),
+      Self::SetCamera => Some("SetCamera"),
+      Self::PushInt32 => Some("PushInt32"),
+      _ => None,
+    }
+  }
+}
+impl core::fmt::Debug for RenderCommandType {
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    if let Some(name) = self.variant_name() {
+      f.write_str(name)
+    } else {
+      f.write_fmt(format_args!("<UNKNOWN {:?}>", self.0))
+    }
+  }
+}
+impl<'a> flatbuffers::Follow<'a> for RenderCommandType {
+  type Inner = Self;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    let b = flatbuffers::read_scalar_at::<i32>(buf, loc);
+    Self(b)
+  }
+}
+
+impl flatbuffers::Push for RenderCommandType {
+    type Output = RenderCommandType;
+    #[inline]
+    unsafe fn push(&self, dst: &mut [u8], _written_len: usize) {
+        flatbuffers::emplace_scalar::<i32>(dst, self.0);
+    }
+}
+
+impl flatbuffers::EndianScalar for RenderCommandType {
+  type Scalar = i32;
+  #[inline]
+  fn to_little_endian(self) -> i32 {
+    self.0.to_le()
+  }
+  #[inline]
+  #[allow(clippy::wrong_self_convention)]
+  fn from_little_endian(v: i32) -> Self {
+    let b = i32::from_le(v);
+    Self(b)
+  }
+}
+
+impl<'a> flatbuffers::Verifiable for RenderCommandType {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    i32::run_verifier(v, pos)
+  }
+}
+
+impl flatbuffers::SimpleToVerifyInSlice for RenderCommandType {}
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+pub const ENUM_MIN_REQUEST_COMMAND_TYPE: i32 = 0;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+pub const ENUM_MAX_REQUEST_COMMAND_TYPE: i32 = 14;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+#[allow(non_camel_case_types)]
+pub const ENUM_VALUES_REQUEST_COMMAND_TYPE: [RequestCommandType; 15] = [
+  RequestCommandType::PushVec2f,
+  RequestCommandType::SetViewportSize,
+  RequestCommandType::OnTouchStart,
+  RequestCommandType::OnTouchEnd,
+  RequestCommandType::OnTouchMove,
+  RequestCommandType::PushVec2i,
+  RequestCommandType::PushInt32,
+  RequestCommandType::OnKeyDown,
+  RequestCommandType::OnKeyUp,
+  RequestCommandType::OnTextInput,
+  RequestCommandType::OnScroll,
+  RequestCommandType::CameraFit,
+  RequestCommandType::CameraGoTo,
+  RequestCommandType::CameraSaveBookmark,
+  RequestCommandType::CameraLoadBookmark,
+];
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[repr(transparent)]
+pub struct RequestCommandType(pub i32);
+#[allow(non_upper_case_globals)]
+impl RequestCommandType {
+  pub const PushVec2f: Self = Self(0);
+  pub const SetViewportSize: Self = Self(1);
+  pub const OnTouchStart: Self = Self(2);
+  pub const OnTouchEnd: Self = Self(3);
+  pub const OnTouchMove: Self = Self(4);
+  pub const PushVec2i: Self = Self(5);
+  pub const PushInt32: Self = Self(6);
+  pub const OnKeyDown: Self = Self(7);
+  pub const OnKeyUp: Self = Self(8);
+  pub const OnTextInput: Self = Self(9);
+  pub const OnScroll: Self = Self(10);
+  pub const CameraFit: Self = Self(11);
+  pub const CameraGoTo: Self = Self(12);
+  pub const CameraSaveBookmark: Self = Self(13);
+  pub const CameraLoadBookmark: Self = Self(14);
+
+  pub const ENUM_MIN: i32 = 0;
+  pub const ENUM_MAX: i32 = 14;
+  pub const ENUM_VALUES: &'static [Self] = &[
+    Self::PushVec2f,
+    Self::SetViewportSize,
+    Self::OnTouchStart,
+    Self::OnTouchEnd,
+    Self::OnTouchMove,
+    Self::PushVec2i,
+    Self::PushInt32,
+    Self::OnKeyDown,
+    Self::OnKeyUp,
+    Self::OnTextInput,
+    Self::OnScroll,
+    Self::CameraFit,
+    Self::CameraGoTo,
+    Self::CameraSaveBookmark,
+    Self::CameraLoadBookmark,
+  ];
+  /// Returns the variant's name or "" if unknown.
+  pub fn variant_name(self) -> Option<&'static str> {
+    match self {
+      Self::PushVec2f => Some("PushVec2f"),
+      Self::SetViewportSize => Some("SetViewportSize"),
+      Self::OnTouchStart => Some("OnTouchStart"),
+      Self::OnTouchEnd => Some("OnTouchEnd"),
+      Self::OnTouchMove => Some("OnTouchMove"),
+      Self::PushVec2i => Some("PushVec2i"),
+      Self::PushInt32 => Some("PushInt32"),
+      Self::OnKeyDown => Some("OnKeyDown"),
+      Self::OnKeyUp => Some("OnKeyUp"),
+      Self::OnTextInput => Some("OnTextInput"),
+      Self::OnScroll => Some("OnScroll"),
+      Self::CameraFit => Some("CameraFit"),
+      Self::CameraGoTo => Some("CameraGoTo"),
+      Self::CameraSaveBookmark => Some("CameraSaveBookmark"),
+      Self::CameraLoadBookmark => Some("CameraLoadBookmark"),
+      _ => None,
+    }
+  }
+}
+impl core::fmt::Debug for RequestCommandType {
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    if let Some(name) = self.variant_name() {
+      f.write_str(name)
+    } else {
+      f.write_fmt(format_args!("<UNKNOWN {:?}>", self.0))
+    }
+  }
+}
+impl<'a> flatbuffers::Follow<'a> for RequestCommandType {
+  type Inner = Self;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    let b = flatbuffers::read_scalar_at::<i32>(buf, loc);
+    Self(b)
+  }
+}
+
+impl flatbuffers::Push for RequestCommandType {
+    type Output = RequestCommandType;
+    #[inline]
+    unsafe fn push(&self, dst: &mut [u8], _written_len: usize) {
+        flatbuffers::emplace_scalar::<i32>(dst, self.0);
+    }
+}
+
+impl flatbuffers::EndianScalar for RequestCommandType {
+  type Scalar = i32;
+  #[inline]
+  fn to_little_endian(self) -> i32 {
+    self.0.to_le()
+  }
+  #[inline]
+  #[allow(clippy::wrong_self_convention)]
+  fn from_little_endian(v: i32) -> Self {
+    let b = i32::from_le(v);
+    Self(b)
+  }
+}
+
+impl<'a> flatbuffers::Verifiable for RequestCommandType {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    i32::run_verifier(v, pos)
+  }
+}
+
+impl flatbuffers::SimpleToVerifyInSlice for RequestCommandType {}
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+pub const ENUM_MIN_EXECUTION_COMMAND_TYPE: i32 = 0;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+pub const ENUM_MAX_EXECUTION_COMMAND_TYPE: i32 = 6;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+#[allow(non_camel_case_types)]
+pub const ENUM_VALUES_EXECUTION_COMMAND_TYPE: [ExecutionCommandType; 7] = [
+  ExecutionCommandType::PushVec2f,
+  ExecutionCommandType::UpdateCameraPosition,
+  ExecutionCommandType::PushInt32,
+  ExecutionCommandType::SetCursor,
+  ExecutionCommandType::UpdateCameraTransform,
+  ExecutionCommandType::UpdateCameraViewPort,
+  ExecutionCommandType::RemoveCamera,
+];
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[repr(transparent)]
+pub struct ExecutionCommandType(pub i32);
+#[allow(non_upper_case_globals)]
+impl ExecutionCommandType {
+  pub const PushVec2f: Self = Self(0);
+  pub const UpdateCameraPosition: Self = Self(1);
+  pub const PushInt32: Self = Self(2);
+  pub const SetCursor: Self = Self(3);
+  pub const UpdateCameraTransform: Self = Self(4);
+  pub const UpdateCameraViewPort: Self = Self(5);
+  pub const RemoveCamera: Self = Self(6);
+
+  pub const ENUM_MIN: i32 = 0;
+  pub const ENUM_MAX: i32 = 6;
+  pub const ENUM_VALUES: &'static [Self] = &[
+    Self::PushVec2f,
+    Self::UpdateCameraPosition,
+    Self::PushInt32,
+    Self::SetCursor,
+    Self::UpdateCameraTransform,
+    Self::UpdateCameraViewPort,
+    Self::RemoveCamera,
+  ];
+  /// Returns the variant's name or "" if unknown.
+  pub fn variant_name(self) -> Option<&'static str> {
+    match self {
+      Self::PushVec2f => Some("PushVec2f"),
+      Self::UpdateCameraPosition => Some("UpdateCameraPosition"),
+      Self::PushInt32 => Some("PushInt32"),
+      Self::SetCursor => Some("SetCursor"),
+      Self::UpdateCameraTransform => Some("UpdateCameraTransform"),
+      Self::UpdateCameraViewPort => Some("UpdateCameraViewPort"),
+      Self::RemoveCamera => Some("RemoveCamera"),
+      _ => None,
+    }
+  }
+}
+impl core::fmt::Debug for ExecutionCommandType {
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    if let Some(name) = self.variant_name() {
+      f.write_str(name)
+    } else {
+      f.write_fmt(format_args!("<UNKNOWN {:?}>", self.0))
+    }
+  }
+}
+impl<'a> flatbuffers::Follow<'a> for ExecutionCommandType {
+  type Inner = Self;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    let b = flatbuffers::read_scalar_at::<i32>(buf, loc);
+    Self(b)
+  }
+}
+
+impl flatbuffers::Push for ExecutionCommandType {
+    type Output = ExecutionCommandType;
+    #[inline]
+    unsafe fn push(&self, dst: &mut [u8], _written_len: usize) {
+        flatbuffers::emplace_scalar::<i32>(dst, self.0);
+    }
+}
+
+impl flatbuffers::EndianScalar for ExecutionCommandType {
+  type Scalar = i32;
+  #[inline]
+  fn to_little_endian(self) -> i32 {
+    self.0.to_le()
+  }
+  #[inline]
+  #[allow(clippy::wrong_self_convention)]
+  fn from_little_endian(v: i32) -> Self {
+    let b = i32::from_le(v);
+    Self(b)
+  }
+}
+
+impl<'a> flatbuffers::Verifiable for ExecutionCommandType {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    i32::run_verifier(v, pos)
+  }
+}
+
+impl flatbuffers::SimpleToVerifyInSlice for ExecutionCommandType {}
+// struct Vec2f, aligned to 4
+#[repr(transparent)]
+#[derive(Clone, Copy, PartialEq)]
+pub struct Vec2f(pub [u8; 8]);
+impl Default for Vec2f { 
+  fn default() -> Self { 
+    Self([0; 8])
+  }
+}
+impl core::fmt::Debug for Vec2f {
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    f.debug_struct("Vec2f")
+      .field("x", &self.x())
+      .field("y", &self.y())
+      .finish()
+  }
+}
+
+impl flatbuffers::SimpleToVerifyInSlice for Vec2f {}
+impl<'a> flatbuffers::Follow<'a> for Vec2f {
+  type Inner = &'a Vec2f;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    <&'a Vec2f>::follow(buf, loc)
+  }
+}
+impl<'a> flatbuffers::Follow<'a> for &'a Vec2f {
+  type Inner = &'a Vec2f;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    flatbuffers::follow_cast_ref::<Vec2f>(buf, loc)
+  }
+}
+impl<'b> flatbuffers::Push for Vec2f {
+    type Output = Vec2f;
+    #[inline]
+    unsafe fn push(&self, dst: &mut [u8], _written_len: usize) {
+        let src = ::core::slice::from_raw_parts(self as *const Vec2f as *const u8, <Self as flatbuffers::Push>::size());
+        dst.copy_from_slice(src);
+    }
+    #[inline]
+    fn alignment() -> flatbuffers::PushAlignment {
+        flatbuffers::PushAlignment::new(4)
+    }
+}
+
+impl<'a> flatbuffers::Verifiable for Vec2f {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.in_buffer::<Self>(pos)
+  }
+}
+
+impl<'a> Vec2f {
+  #[allow(clippy::too_many_arguments)]
+  pub fn new(
+    x: f32,
+    y: f32,
+  ) -> Self {
+    let mut s = Self([0; 8]);
+    s.set_x(x);
+    s.set_y(y);
+    s
+  }
+
+  pub fn x(&self) -> f32 {
+    let mut mem = core::mem::MaybeUninit::<<f32 as EndianScalar>::Scalar>::uninit();
+    // Safety:
+    // Created from a valid Table for this object
+    // Which contains a valid value in this slot
+    EndianScalar::from_little_endian(unsafe {
+      core::ptr::copy_nonoverlapping(
+        self.0[0..].as_ptr(),
+        mem.as_mut_ptr() as *mut u8,
+        core::mem::size_of::<<f32 as EndianScalar>::Scalar>(),
+      );
+      mem.assume_init()
+    })
+  }
+
+  pub fn set_x(&mut self, x: f32) {
+    let x_le = x.to_little_endian();
+    // Safety:
+    // Created from a valid Table for this object
+    // Which contains a valid value in this slot
+    unsafe {
+      core::ptr::copy_nonoverlapping(
+        &x_le as *const _ as *const u8,
+        self.0[0..].as_mut_ptr(),
+        core::mem::size_of::<<f32 as EndianScalar>::Scalar>(),
+      );
+    }
+  }
+
+  pub fn y(&self) -> f32 {
+    let mut mem = core::mem::MaybeUninit::<<f32 as EndianScalar>::Scalar>::uninit();
+    // Safety:
+    // Created from a valid Table for this object
+    // Which contains a valid value in this slot
+    EndianScalar::from_little_endian(unsafe {
+      core::ptr::copy_nonoverlapping(
+        self.0[4..].as_ptr(),
+        mem.as_mut_ptr() as *mut u8,
+        core::mem::size_of::<<f32 as EndianScalar>::Scalar>(),
+      );
+      mem.assume_init()
+    })
+  }
+
+  pub fn set_y(&mut self, x: f32) {
+    let x_le = x.to_little_endian();
+    // Safety:
+    // Created from a valid Table for this object
+    // Which contains a valid value in this slot
+    unsafe {
+      core::ptr::copy_nonoverlapping(
+        &x_le as *const _ as *const u8,
+        self.0[4..].as_mut_ptr(),
+        core::mem::size_of::<<f32 as EndianScalar>::Scalar>(),
+      );
+    }
+  }
+
+}
+
+// struct Vec2i, aligned to 4
+#[repr(transparent)]
+#[derive(Clone, Copy, PartialEq)]
+pub struct Vec2i(pub [u8; 8]);
+impl Default for Vec2i { 
+  fn default() -> Self { 
+    Self([0; 8])
+  }
+}
+impl core::fmt::Debug for Vec2i {
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    f.debug_struct("Vec2i")
+      .field("x", &self.x())
+      .field("y", &self.y())
+      .finish()
+  }
+}
+
+impl flatbuffers::SimpleToVerifyInSlice for Vec2i {}
+impl<'a> flatbuffers::Follow<'a> for Vec2i {
+  type Inner = &'a Vec2i;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    <&'a Vec2i>::follow(buf, loc)
+  }
+}
+impl<'a> flatbuffers::Follow<'a> for &'a Vec2i {
+  type Inner = &'a Vec2i;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    flatbuffers::follow_cast_ref::<Vec2i>(buf, loc)
+  }
+}
+impl<'b> flatbuffers::Push for Vec2i {
+    type Output = Vec2i;
+    #[inline]
+    unsafe fn push(&self, dst: &mut [u8], _written_len: usize) {
+        let src = ::core::slice::from_raw_parts(self as *const Vec2i as *const u8, <Self as flatbuffers::Push>::size());
+        dst.copy_from_slice(src);
+    }
+    #[inline]
+    fn alignment() -> flatbuffers::PushAlignment {
+        flatbuffers::PushAlignment::new(4)
+    }
+}
+
+impl<'a> flatbuffers::Verifiable for Vec2i {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.in_buffer::<Self>(pos)
+  }
+}
+
+impl<'a> Vec2i {
+  #[allow(clippy::too_many_arguments)]
+  pub fn new(
+    x: i32,
+    y: i32,
+  ) -> Self {
+    let mut s = Self([0; 8]);
+    s.set_x(x);
+    s.set_y(y);
+    s
+  }
+
+  pub fn x(&self) -> i32 {
+    let mut mem = core::mem::MaybeUninit::<<i32 as EndianScalar>::Scalar>::uninit();
+    // Safety:
+    // Created from a valid Table for this object
+    // Which contains a valid value in this slot
+    EndianScalar::from_little_endian(unsafe {
+      core::ptr::copy_nonoverlapping(
+        self.0[0..].as_ptr(),
+        mem.as_mut_ptr() as *mut u8,
+        core::mem::size_of::<<i32 as EndianScalar>::Scalar>(),
+      );
+      mem.assume_init()
+    })
+  }
+
+  pub fn set_x(&mut self, x: i32) {
+    let x_le = x.to_little_endian();
+    // Safety:
+    // Created from a valid Table for this object
+    // Which contains a valid value in this slot
+    unsafe {
+      core::ptr::copy_nonoverlapping(
+        &x_le as *const _ as *const u8,
+        self.0[0..].as_mut_ptr(),
+        core::mem::size_of::<<i32 as EndianScalar>::Scalar>(),
+      );
+    }
+  }
+
+  pub fn y(&self) -> i32 {
+    let mut mem = core::mem::MaybeUninit::<<i32 as EndianScalar>::Scalar>::uninit();
+    // Safety:
+    // Created from a valid Table for this object
+    // Which contains a valid value in this slot
+    EndianScalar::from_little_endian(unsafe {
+      core::ptr::copy_nonoverlapping(
+        self.0[4..].as_ptr(),
+        mem.as_mut_ptr() as *mut u8,
+        core::mem::size_of::<<i32 as EndianScalar>::Scalar>(),
+      );
+      mem.assume_init()
+    })
+  }
+
+  pub fn set_y(&mut self, x: i32) {
+    let x_le = x.to_little_endian();
+    // Safety:
+    // Created from a valid Table for this object
+    // Which contains a valid value in this slot
+    unsafe {
+      core::ptr::copy_nonoverlapping(
+        &x_le as *const _ as *const u8,
+        self.0[4..].as_mut_ptr(),
+        core::mem::size_of::<<i32 as EndianScalar>::Scalar>(),
+      );
+    }
+  }
+
+}
+
+// struct Color, aligned to 4
+#[repr(transparent)]
+#[derive(Clone, Copy, PartialEq)]
+pub struct Color(pub [u8; 16]);
+impl Default for Color { 
+  fn default() -> Self { 
+    Self([0; 16])
+  }
+}
+impl core::fmt::Debug for Color {
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    f.debug_struct("Color")
+      .field("r", &self.r())
+      .field("g", &self.g())
+      .field("b", &self.b())
+      .field("a", &self.a())
+      .finish()
+  }
+}
+
+impl flatbuffers::SimpleToVerifyInSlice for Color {}
+impl<'a> flatbuffers::Follow<'a> for Color {
+  type Inner = &'a Color;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    <&'a Color>::follow(buf, loc)
+  }
+}
+impl<'a> flatbuffers::Follow<'a> for &'a Color {
+  type Inner = &'a Color;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    flatbuffers::follow_cast_ref::<Color>(buf, loc)
+  }
+}
+impl<'b> flatbuffers::Push for Color {
+    type Output = Color;
+    #[inline]
+    unsafe fn push(&self, dst: &mut [u8], _written_len: usize) {
+        let src = ::core::slice::from_raw_parts(self as *const Color as *const u8, <Self as flatbuffers::Push>::size());
+        dst.copy_from_slice(src);
+    }
+    #[inline]
+    fn alignment() -> flatbuffers::PushAlignment {
+        flatbuffers::PushAlignment::new(4)
+    }
+}
+
+impl<'a> flatbuffers::Verifiable for Color {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.in_buffer::<Self>(pos)
+  }
+}
+
+impl<'a> Color {
+  #[allow(clippy::too_many_arguments)]
+  pub fn new(
+    r: f32,
+    g: f32,
+    b: f32,
+    a: f32,
+  ) -> Self {
+    let mut s = Self([0; 16]);
+    s.set_r(r);
+    s.set_g(g);
+    s.set_b(b);
+    s.set_a(a);
+    s
+  }
+
+  pub fn r(&self) -> f32 {
+    let mut mem = core::mem::MaybeUninit::<<f32 as EndianScalar>::Scalar>::uninit();
+    // Safety:
+    // Created from a valid Table for this object
+    // Which contains a valid value in this slot
+    EndianScalar::from_little_endian(unsafe {
+      core::ptr::copy_nonoverlapping(
+        self.0[0..].as_ptr(),
+        mem.as_mut_ptr() as *mut u8,
+        core::mem::size_of::<<f32 as EndianScalar>::Scalar>(),
+      );
+      mem.assume_init()
+    })
+  }
+
+  pub fn set_r(&mut self, x: f32) {
+    let x_le = x.to_little_endian();
+    // Safety:
+    // Created from a valid Table for this object
+    // Which contains a valid value in this slot
+    unsafe {
+      core::ptr::copy_nonoverlapping(
+        &x_le as *const _ as *const u8,
+        self.0[0..].as_mut_ptr(),
+        core::mem::size_of::<<f32 as EndianScalar>::Scalar>(),
+      );
+    }
+  }
+
+  pub fn g(&self) -> f32 {
+    let mut mem = core::mem::MaybeUninit::<<f32 as EndianScalar>::Scalar>::uninit();
+    // Safety:
+    // Created from a valid Table for this object
+    // Which contains a valid value in this slot
+    EndianScalar::from_little_endian(unsafe {
+      core::ptr::copy_nonoverlapping(
+        self.0[4..].as_ptr(),
+        mem.as_mut_ptr() as *mut u8,
+        core::mem::size_of::<<f32 as EndianScalar>::Scalar>(),
+      );
+      mem.assume_init()
+    })
+  }
+
+  pub fn set_g(&mut self, x: f32) {
+    let x_le = x.to_little_endian();
+    // Safety:
+    // Created from a valid Table for this object
+    // Which contains a valid value in this slot
+    unsafe {
+      core::ptr::copy_nonoverlapping(
+        &x_le as *const _ as *const u8,
+        self.0[4..].as_mut_ptr(),
+        core::mem::size_of::<<f32 as EndianScalar>::Scalar>(),
+      );
+    }
+  }
+
+  pub fn b(&self) -> f32 {
+    let mut mem = core::mem::MaybeUninit::<<f32 as EndianScalar>::Scalar>::uninit();
+    // Safety:
+    // Created from a valid Table for this object
+    // Which contains a valid value in this slot
+    EndianScalar::from_little_endian(unsafe {
+      core::ptr::copy_nonoverlapping(
+        self.0[8..].as_ptr(),
+        mem.as_mut_ptr() as *mut u8,
+        core::mem::size_of::<<f32 as EndianScalar>::Scalar>(),
+      );
+      mem.assume_init()
+    })
+  }
+
+  pub fn set_b(&mut self, x: f32) {
+    let x_le = x.to_little_endian();
+    // Safety:
+    // Created from a valid Table for this object
+    // Which contains a valid value in this slot
+    unsafe {
+      core::ptr::copy_nonoverlapping(
+        &x_le as *const _ as *const u8,
+        self.0[8..].as_mut_ptr(),
+        core::mem::size_of::<<f32 as EndianScalar>::Scalar>(),
+      );
+    }
+  }
+
+  pub fn a(&self) -> f32 {
+    let mut mem = core::mem::MaybeUninit::<<f32 as EndianScalar>::Scalar>::uninit();
+    // Safety:
+    // Created from a valid Table for this object
+    // Which contains a valid value in this slot
+    EndianScalar::from_little_endian(unsafe {
+      core::ptr::copy_nonoverlapping(
+        self.0[12..].as_ptr(),
+        mem.as_mut_ptr() as *mut u8,
+        core::mem::size_of::<<f32 as EndianScalar>::Scalar>(),
+      );
+      mem.assume_init()
+    })
+  }
+
+  pub fn set_a(&mut self, x: f32) {
+    let x_le = x.to_little_endian();
+    // Safety:
+    // Created from a valid Table for this object
+    // Which contains a valid value in this slot
+    unsafe {
+      core::ptr::copy_nonoverlapping(
+        &x_le as *const _ as *const u8,
+        self.0[12..].as_mut_ptr(),
+        core::mem::size_of::<<f32 as EndianScalar>::Scalar>(),
+      );
+    }
+  }
+
+}
+
+pub enum CommandDataOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct CommandData<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for CommandData<'a> {
+  type Inner = CommandData<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> CommandData<'a> {
+  pub const VT_INT32: flatbuffers::VOffsetT = 4;
+  pub const VT_VEC2F: flatbuffers::VOffsetT = 6;
+  pub const VT_VEC2I: flatbuffers::VOffsetT = 8;
+  pub const VT_COLOR: flatbuffers::VOffsetT = 10;
+  pub const VT_STRING: flatbuffers::VOffsetT = 12;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    CommandData { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr, A: flatbuffers::Allocator + 'bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+    args: &'args CommandDataArgs<'args>
+  ) -> flatbuffers::WIPOffset<CommandData<'bldr>> {
+    let mut builder = CommandDataBuilder::new(_fbb);
+    if let Some(x) = args.string { builder.add_string(x); }
+    if let Some(x) = args.color { builder.add_color(x); }
+    if let Some(x) = args.vec2i { builder.add_vec2i(x); }
+    if let Some(x) = args.vec2f { builder.add_vec2f(x); }
+    builder.add_int32(args.int32);
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn int32(&self) -> i32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<i32>(CommandData::VT_INT32, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn vec2f(&self) -> Option<&'a Vec2f> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<Vec2f>(CommandData::VT_VEC2F, None)}
+  }
+  #[inline]
+  pub fn vec2i(&self) -> Option<&'a Vec2i> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<Vec2i>(CommandData::VT_VEC2I, None)}
+  }
+  #[inline]
+  pub fn color(&self) -> Option<&'a Color> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<Color>(CommandData::VT_COLOR, None)}
+  }
+  #[inline]
+  pub fn string(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(CommandData::VT_STRING, None)}
+  }
+}
+
+impl flatbuffers::Verifiable for CommandData<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<i32>("int32", Self::VT_INT32, false)?
+     .visit_field::<Vec2f>("vec2f", Self::VT_VEC2F, false)?
+     .visit_field::<Vec2i>("vec2i", Self::VT_VEC2I, false)?
+     .visit_field::<Color>("color", Self::VT_COLOR, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("string", Self::VT_STRING, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct CommandDataArgs<'a> {
+    pub int32: i32,
+    pub vec2f: Option<&'a Vec2f>,
+    pub vec2i: Option<&'a Vec2i>,
+    pub color: Option<&'a Color>,
+    pub string: Option<flatbuffers::WIPOffset<&'a str>>,
+}
+impl<'a> Default for CommandDataArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    CommandDataArgs {
+      int32: 0,
+      vec2f: None,
+      vec2i: None,
+      color: None,
+      string: None,
+    }
+  }
+}
+
+pub struct CommandDataBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> CommandDataBuilder<'a, 'b, A> {
+  #[inline]
+  pub fn add_int32(&mut self, int32: i32) {
+    self.fbb_.push_slot::<i32>(CommandData::VT_INT32, int32, 0);
+  }
+  #[inline]
+  pub fn add_vec2f(&mut self, vec2f: &Vec2f) {
+    self.fbb_.push_slot_always::<&Vec2f>(CommandData::VT_VEC2F, vec2f);
+  }
+  #[inline]
+  pub fn add_vec2i(&mut self, vec2i: &Vec2i) {
+    self.fbb_.push_slot_always::<&Vec2i>(CommandData::VT_VEC2I, vec2i);
+  }
+  #[inline]
+  pub fn add_color(&mut self, color: &Color) {
+    self.fbb_.push_slot_always::<&Color>(CommandData::VT_COLOR, color);
+  }
+  #[inline]
+  pub fn add_string(&mut self, string: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(CommandData::VT_STRING, string);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> CommandDataBuilder<'a, 'b, A> {
+    let start = _fbb.start_table();
+    CommandDataBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<CommandData<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for CommandData<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("CommandData");
+      ds.field("int32", &self.int32());
+      ds.field("vec2f", &self.vec2f());
+      ds.field("vec2i", &self.vec2i());
+      ds.field("color", &self.color());
+      ds.field("string", &self.string());
+      ds.finish()
+  }
+}
+pub enum RenderCommandOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct RenderCommand<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for RenderCommand<'a> {
+  type Inner = RenderCommand<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> RenderCommand<'a> {
+  pub const VT_TYPE_: flatbuffers::VOffsetT = 4;
+  pub const VT_DATA: flatbuffers::VOffsetT = 6;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    RenderCommand { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr, A: flatbuffers::Allocator + 'bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+    args: &'args RenderCommandArgs<'args>
+  ) -> flatbuffers::WIPOffset<RenderCommand<'bldr>> {
+    let mut builder = RenderCommandBuilder::new(_fbb);
+    if let Some(x) = args.data { builder.add_data(x); }
+    builder.add_type_(args.type_);
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn type_(&self) -> RenderCommandType {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<RenderCommandType>(RenderCommand::VT_TYPE_, Some(RenderCommandType::PushColor)).unwrap()}
+  }
+  #[inline]
+  pub fn data(&self) -> Option<CommandData<'a>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<CommandData>>(RenderCommand::VT_DATA, None)}
+  }
+}
+
+impl flatbuffers::Verifiable for RenderCommand<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<RenderCommandType>("type_", Self::VT_TYPE_, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<CommandData>>("data", Self::VT_DATA, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct RenderCommandArgs<'a> {
+    pub type_: RenderCommandType,
+    pub data: Option<flatbuffers::WIPOffset<CommandData<'a>>>,
+}
+impl<'a> Default for RenderCommandArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    RenderCommandArgs {
+      type_: RenderCommandType::PushColor,
+      data: None,
+    }
+  }
+}
+
+pub struct RenderCommandBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> RenderCommandBuilder<'a, 'b, A> {
+  #[inline]
+  pub fn add_type_(&mut self, type_: RenderCommandType) {
+    self.fbb_.push_slot::<RenderCommandType>(RenderCommand::VT_TYPE_, type_, RenderCommandType::PushColor);
+  }
+  #[inline]
+  pub fn add_data(&mut self, data: flatbuffers::WIPOffset<CommandData<'b >>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<CommandData>>(RenderCommand::VT_DATA, data);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> RenderCommandBuilder<'a, 'b, A> {
+    let start = _fbb.start_table();
+    RenderCommandBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<RenderCommand<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for RenderCommand<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("RenderCommand");
+      ds.field("type_", &self.type_());
+      ds.field("data", &self.data());
+      ds.finish()
+  }
+}
+pub enum ExecutionCommandOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct ExecutionCommand<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for ExecutionCommand<'a> {
+  type Inner = ExecutionCommand<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> ExecutionCommand<'a> {
+  pub const VT_TYPE_: flatbuffers::VOffsetT = 4;
+  pub const VT_DATA: flatbuffers::VOffsetT = 6;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    ExecutionCommand { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr, A: flatbuffers::Allocator + 'bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+    args: &'args ExecutionCommandArgs<'args>
+  ) -> flatbuffers::WIPOffset<ExecutionCommand<'bldr>> {
+    let mut builder = ExecutionCommandBuilder::new(_fbb);
+    if let Some(x) = args.data { builder.add_data(x); }
+    builder.add_type_(args.type_);
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn type_(&self) -> ExecutionCommandType {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<ExecutionCommandType>(ExecutionCommand::VT_TYPE_, Some(ExecutionCommandType::PushVec2f)).unwrap()}
+  }
+  #[inline]
+  pub fn data(&self) -> Option<CommandData<'a>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<CommandData>>(ExecutionCommand::VT_DATA, None)}
+  }
+}
+
+impl flatbuffers::Verifiable for ExecutionCommand<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<ExecutionCommandType>("type_", Self::VT_TYPE_, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<CommandData>>("data", Self::VT_DATA, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct ExecutionCommandArgs<'a> {
+    pub type_: ExecutionCommandType,
+    pub data: Option<flatbuffers::WIPOffset<CommandData<'a>>>,
+}
+impl<'a> Default for ExecutionCommandArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    ExecutionCommandArgs {
+      type_: ExecutionCommandType::PushVec2f,
+      data: None,
+    }
+  }
+}
+
+pub struct ExecutionCommandBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> ExecutionCommandBuilder<'a, 'b, A> {
+  #[inline]
+  pub fn add_type_(&mut self, type_: ExecutionCommandType) {
+    self.fbb_.push_slot::<ExecutionCommandType>(ExecutionCommand::VT_TYPE_, type_, ExecutionCommandType::PushVec2f);
+  }
+  #[inline]
+  pub fn add_data(&mut self, data: flatbuffers::WIPOffset<CommandData<'b >>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<CommandData>>(ExecutionCommand::VT_DATA, data);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> ExecutionCommandBuilder<'a, 'b, A> {
+    let start = _fbb.start_table();
+    ExecutionCommandBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<ExecutionCommand<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for ExecutionCommand<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("ExecutionCommand");
+      ds.field("type_", &self.type_());
+      ds.field("data", &self.data());
+      ds.finish()
+  }
+}
+pub enum RequestCommandOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct RequestCommand<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for RequestCommand<'a> {
+  type Inner = RequestCommand<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> RequestCommand<'a> {
+  pub const VT_TYPE_: flatbuffers::VOffsetT = 4;
+  pub const VT_DATA: flatbuffers::VOffsetT = 6;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    RequestCommand { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr, A: flatbuffers::Allocator + 'bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+    args: &'args RequestCommandArgs<'args>
+  ) -> flatbuffers::WIPOffset<RequestCommand<'bldr>> {
+    let mut builder = RequestCommandBuilder::new(_fbb);
+    if let Some(x) = args.data { builder.add_data(x); }
+    builder.add_type_(args.type_);
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn type_(&self) -> RequestCommandType {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<RequestCommandType>(RequestCommand::VT_TYPE_, Some(RequestCommandType::PushVec2f)).unwrap()}
+  }
+  #[inline]
+  pub fn data(&self) -> Option<CommandData<'a>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<CommandData>>(RequestCommand::VT_DATA, None)}
+  }
+}
+
+impl flatbuffers::Verifiable for RequestCommand<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<RequestCommandType>("type_", Self::VT_TYPE_, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<CommandData>>("data", Self::VT_DATA, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct RequestCommandArgs<'a> {
+    pub type_: RequestCommandType,
+    pub data: Option<flatbuffers::WIPOffset<CommandData<'a>>>,
+}
+impl<'a> Default for RequestCommandArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    RequestCommandArgs {
+      type_: RequestCommandType::PushVec2f,
+      data: None,
+    }
+  }
+}
+
+pub struct RequestCommandBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> RequestCommandBuilder<'a, 'b, A> {
+  #[inline]
+  pub fn add_type_(&mut self, type_: RequestCommandType) {
+    self.fbb_.push_slot::<RequestCommandType>(RequestCommand::VT_TYPE_, type_, RequestCommandType::PushVec2f);
+  }
+  #[inline]
+  pub fn add_data(&mut self, data: flatbuffers::WIPOffset<CommandData<'b >>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<CommandData>>(RequestCommand::VT_DATA, data);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> RequestCommandBuilder<'a, 'b, A> {
+    let start = _fbb.start_table();
+    RequestCommandBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<RequestCommand<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for RequestCommand<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("RequestCommand");
+      ds.field("type_", &self.type_());
+      ds.field("data", &self.data());
+      ds.finish()
+  }
+}
+pub enum RenderCommandsOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct RenderCommands<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for RenderCommands<'a> {
+  type Inner = RenderCommands<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> RenderCommands<'a> {
+  pub const VT_COMMANDS: flatbuffers::VOffsetT = 4;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    RenderCommands { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr, A: flatbuffers::Allocator + 'bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+    args: &'args RenderCommandsArgs<'args>
+  ) -> flatbuffers::WIPOffset<RenderCommands<'bldr>> {
+    let mut builder = RenderCommandsBuilder::new(_fbb);
+    if let Some(x) = args.commands { builder.add_commands(x); }
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn commands(&self) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<RenderCommand<'a>>>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<RenderCommand>>>>(RenderCommands::VT_COMMANDS, None)}
+  }
+}
+
+impl flatbuffers::Verifiable for RenderCommands<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<RenderCommand>>>>("commands", Self::VT_COMMANDS, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct RenderCommandsArgs<'a> {
+    pub commands: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<RenderCommand<'a>>>>>,
+}
+impl<'a> Default for RenderCommandsArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    RenderCommandsArgs {
+      commands: None,
+    }
+  }
+}
+
+pub struct RenderCommandsBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> RenderCommandsBuilder<'a, 'b, A> {
+  #[inline]
+  pub fn add_commands(&mut self, commands: flatbuffers::WIPOffset<flatbuffers::Vector<'b , flatbuffers::ForwardsUOffset<RenderCommand<'b >>>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(RenderCommands::VT_COMMANDS, commands);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> RenderCommandsBuilder<'a, 'b, A> {
+    let start = _fbb.start_table();
+    RenderCommandsBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<RenderCommands<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for RenderCommands<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("RenderCommands");
+      ds.field("commands", &self.commands());
+      ds.finish()
+  }
+}
+pub enum ExecutionCommandsOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct ExecutionCommands<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for ExecutionCommands<'a> {
+  type Inner = ExecutionCommands<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> ExecutionCommands<'a> {
+  pub const VT_COMMANDS: flatbuffers::VOffsetT = 4;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    ExecutionCommands { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr, A: flatbuffers::Allocator + 'bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+    args: &'args ExecutionCommandsArgs<'args>
+  ) -> flatbuffers::WIPOffset<ExecutionCommands<'bldr>> {
+    let mut builder = ExecutionCommandsBuilder::new(_fbb);
+    if let Some(x) = args.commands { builder.add_commands(x); }
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn commands(&self) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<ExecutionCommand<'a>>>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<ExecutionCommand>>>>(ExecutionCommands::VT_COMMANDS, None)}
+  }
+}
+
+impl flatbuffers::Verifiable for ExecutionCommands<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<ExecutionCommand>>>>("commands", Self::VT_COMMANDS, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct ExecutionCommandsArgs<'a> {
+    pub commands: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<ExecutionCommand<'a>>>>>,
+}
+impl<'a> Default for ExecutionCommandsArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    ExecutionCommandsArgs {
+      commands: None,
+    }
+  }
+}
+
+pub struct ExecutionCommandsBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> ExecutionCommandsBuilder<'a, 'b, A> {
+  #[inline]
+  pub fn add_commands(&mut self, commands: flatbuffers::WIPOffset<flatbuffers::Vector<'b , flatbuffers::ForwardsUOffset<ExecutionCommand<'b >>>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ExecutionCommands::VT_COMMANDS, commands);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> ExecutionCommandsBuilder<'a, 'b, A> {
+    let start = _fbb.start_table();
+    ExecutionCommandsBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<ExecutionCommands<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for ExecutionCommands<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("ExecutionCommands");
+      ds.field("commands", &self.commands());
+      ds.finish()
+  }
+}
+pub enum RequestCommandsOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct RequestCommands<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for RequestCommands<'a> {
+  type Inner = RequestCommands<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> RequestCommands<'a> {
+  pub const VT_COMMANDS: flatbuffers::VOffsetT = 4;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    RequestCommands { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr, A: flatbuffers::Allocator + 'bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+    args: &'args RequestCommandsArgs<'args>
+  ) -> flatbuffers::WIPOffset<RequestCommands<'bldr>> {
+    let mut builder = RequestCommandsBuilder::new(_fbb);
+    if let Some(x) = args.commands { builder.add_commands(x); }
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn commands(&self) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<RequestCommand<'a>>>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<RequestCommand>>>>(RequestCommands::VT_COMMANDS, None)}
+  }
+}
+
+impl flatbuffers::Verifiable for RequestCommands<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<RequestCommand>>>>("commands", Self::VT_COMMANDS, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct RequestCommandsArgs<'a> {
+    pub commands: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<RequestCommand<'a>>>>>,
+}
+impl<'a> Default for RequestCommandsArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    RequestCommandsArgs {
+      commands: None,
+    }
+  }
+}
+
+pub struct RequestCommandsBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> RequestCommandsBuilder<'a, 'b, A> {
+  #[inline]
+  pub fn add_commands(&mut self, commands: flatbuffers::WIPOffset<flatbuffers::Vector<'b , flatbuffers::ForwardsUOffset<RequestCommand<'b >>>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(RequestCommands::VT_COMMANDS, commands);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> RequestCommandsBuilder<'a, 'b, A> {
+    let start = _fbb.start_table();
+    RequestCommandsBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<RequestCommands<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for RequestCommands<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("RequestCommands");
+      ds.field("commands", &self.commands());
+      ds.finish()
+  }
+}
+#[inline]
+/// Verifies that a buffer of bytes contains a `RenderCommands`
+/// and returns it.
+/// Note that verification is still experimental and may not
+/// catch every error, or be maximally performant. For the
+/// previous, unchecked, behavior use
+/// `root_as_render_commands_unchecked`.
+pub fn root_as_render_commands(buf: &[u8]) -> Result<RenderCommands, flatbuffers::InvalidFlatbuffer> {
+  flatbuffers::root::<RenderCommands>(buf)
+}
+#[inline]
+/// Verifies that a buffer of bytes contains a size prefixed
+/// `RenderCommands` and returns it.
+/// Note that verification is still experimental and may not
+/// catch every error, or be maximally performant. For the
+/// previous, unchecked, behavior use
+/// `size_prefixed_root_as_render_commands_unchecked`.
+pub fn size_prefixed_root_as_render_commands(buf: &[u8]) -> Result<RenderCommands, flatbuffers::InvalidFlatbuffer> {
+  flatbuffers::size_prefixed_root::<RenderCommands>(buf)
+}
+#[inline]
+/// Assumes, without verification, that a buffer of bytes contains a RenderCommands and returns it.
+/// # Safety
+/// Callers must trust the given bytes do indeed contain a valid `RenderCommands`.
+pub unsafe fn root_as_render_commands_unchecked(buf: &[u8]) -> RenderCommands {
+  flatbuffers::root_unchecked::<RenderCommands>(buf)
+}
+#[inline]
+pub fn finish_render_commands_buffer<'a, 'b, A: flatbuffers::Allocator + 'a>(
+    fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+    root: flatbuffers::WIPOffset<RenderCommands<'a>>) {
+  fbb.finish(root, None);
+}
+
+#[inline]
+pub fn finish_size_prefixed_render_commands_buffer<'a, 'b, A: flatbuffers::Allocator + 'a>(fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>, root: flatbuffers::WIPOffset<RenderCommands<'a>>) {
+  fbb.finish_size_prefixed(root, None);
+}
+}  // pub mod schemes
+}  // pub mod tech_paws
+
@@ -0,0 +1,17 @@
+#!/bin/sh
+# Regenerates the checked-in bindings in schemes/target/rust from the schemas.
+# flatc has to match the version of the flatbuffers crate in Cargo.toml,
+# the capnp plugin comes from `cargo install capnpc` of the capnp crate version.
+set -e
+
+cd "$(dirname "$0")/.."
+
+FLATC_VERSION=$(sed -n 's/^flatbuffers = "\(.*\)"$/\1/p' Cargo.toml)
+
+if [ "$(flatc --version)" != "flatc version $FLATC_VERSION" ]; then
+    echo "flatc $FLATC_VERSION is required, found: $(flatc --version)" >&2
+    exit 1
+fi
+
+flatc --rust -o schemes/target/rust schemes/commands.fbs
+capnp compile -orust:schemes/target/rust --src-prefix=schemes schemes/commands.capnp
@@ -4,7 +4,7 @@ use std::default::Default;
 use std::ops;
 
 #[repr(C)]
-#[derive(Debug, Copy, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Copy, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Vec2f {
     pub x: f32,
     pub y: f32,
@@ -16,6 +16,10 @@ impl Vec2f {
     pub const fn new(x: f32, y: f32) -> Vec2f {
         Vec2f { x, y }
     }
+
+    pub fn length(&self) -> f32 {
+        (self.x * self.x + self.y * self.y).sqrt()
+    }
 }
 
 impl ops::Add<Vec2f> for Vec2f {
@@ -33,8 +37,32 @@ impl ops::AddAssign<Vec2f> for Vec2f {
     }
 }
 
+impl ops::Sub<Vec2f> for Vec2f {
+    type Output = Vec2f;
+
+    fn sub(self, rhs: Vec2f) -> Vec2f {
+        Vec2f::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl ops::Mul<f32> for Vec2f {
+    type Output = Vec2f;
+
+    fn mul(self, rhs: f32) -> Vec2f {
+        Vec2f::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl ops::Div<f32> for Vec2f {
+    type Output = Vec2f;
+
+    fn div(self, rhs: f32) -> Vec2f {
+        Vec2f::new(self.x / rhs, self.y / rhs)
+    }
+}
+
 #[repr(C)]
-#[derive(Debug, Copy, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Copy, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Vec2i {
     pub x: i32,
     pub y: i32,
@@ -51,7 +79,7 @@ impl Vec2i {
 }
 
 #[repr(C)]
-#[derive(Debug, Copy, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Copy, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Rect {
     pub pos: Vec2f,
     pub size: Vec2f,
@@ -63,10 +91,121 @@ impl Rect {
     pub const fn new(pos: Vec2f, size: Vec2f) -> Rect {
         Rect { pos, size }
     }
+
+    pub fn contains(&self, point: Vec2f) -> bool {
+        point.x >= self.pos.x
+            && point.x <= self.pos.x + self.size.x
+            && point.y >= self.pos.y
+            && point.y <= self.pos.y + self.size.y
+    }
+
+    pub fn center(&self) -> Vec2f {
+        self.pos + self.size / 2.0
+    }
+
+    pub fn intersects(&self, other: &Rect) -> bool {
+        self.pos.x <= other.pos.x + other.size.x
+            && other.pos.x <= self.pos.x + self.size.x
+            && self.pos.y <= other.pos.y + other.size.y
+            && other.pos.y <= self.pos.y + self.size.y
+    }
+
+    /// Smallest rect that contains all of the points.
+    pub fn from_points(points: &[Vec2f]) -> Rect {
+        let first = points.first().copied().unwrap_or(Vec2f::ZERO);
+        let (min, max) = points.iter().fold((first, first), |(min, max), point| {
+            (
+                Vec2f::new(min.x.min(point.x), min.y.min(point.y)),
+                Vec2f::new(max.x.max(point.x), max.y.max(point.y)),
+            )
+        });
+
+        Rect::new(min, max - min)
+    }
+
+    pub fn corners(&self) -> [Vec2f; 4] {
+        [
+            self.pos,
+            Vec2f::new(self.pos.x + self.size.x, self.pos.y),
+            Vec2f::new(self.pos.x, self.pos.y + self.size.y),
+            self.pos + self.size,
+        ]
+    }
+}
+
+/// Affine transform, the columns of a 3x3 matrix with the implied `0 0 1` bottom row.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Transform2D {
+    pub x_axis: Vec2f,
+    pub y_axis: Vec2f,
+    pub translation: Vec2f,
+}
+
+impl Transform2D {
+    pub const IDENTITY: Transform2D = Transform2D {
+        x_axis: Vec2f::new(1.0, 0.0),
+        y_axis: Vec2f::new(0.0, 1.0),
+        translation: Vec2f::ZERO,
+    };
+
+    /// Scales, then rotates by `rotation` radians, then translates.
+    pub fn new(translation: Vec2f, rotation: f32, scale: f32) -> Transform2D {
+        let (sin, cos) = rotation.sin_cos();
+
+        Transform2D {
+            x_axis: Vec2f::new(cos, sin) * scale,
+            y_axis: Vec2f::new(-sin, cos) * scale,
+            translation,
+        }
+    }
+
+    pub fn apply(&self, point: Vec2f) -> Vec2f {
+        self.apply_vector(point) + self.translation
+    }
+
+    /// Applies the transform without the translation.
+    pub fn apply_vector(&self, vector: Vec2f) -> Vec2f {
+        self.x_axis * vector.x + self.y_axis * vector.y
+    }
+
+    /// Bounding rect of the transformed corners of `rect`.
+    pub fn apply_rect(&self, rect: Rect) -> Rect {
+        Rect::from_points(&rect.corners().map(|corner| self.apply(corner)))
+    }
+
+    /// Length of the transformed unit vector, the transform has a uniform scale.
+    pub fn scale(&self) -> f32 {
+        self.x_axis.length()
+    }
+
+    pub fn inverse(&self) -> Transform2D {
+        let det = self.x_axis.x * self.y_axis.y - self.y_axis.x * self.x_axis.y;
+
+        if det == 0.0 {
+            return Transform2D::IDENTITY;
+        }
+
+        let x_axis = Vec2f::new(self.y_axis.y, -self.x_axis.y) / det;
+        let y_axis = Vec2f::new(-self.y_axis.x, self.x_axis.x) / det;
+        let translation = (x_axis * self.translation.x + y_axis * self.translation.y) * -1.0;
+
+        Transform2D {
+            x_axis,
+            y_axis,
+            translation,
+        }
+    }
+}
+
+impl Default for Transform2D {
+    fn default() -> Self {
+        Transform2D::IDENTITY
+    }
 }
 
 #[repr(C)]
-#[derive(Debug, Copy, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Copy, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Color {
     pub r: f32,
     pub g: f32,
@@ -85,7 +224,7 @@ impl Color {
 }
 
 #[repr(C)]
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct CommandData {
     pub int32: i32,
     pub vec2f: Vec2f,
@@ -158,7 +297,7 @@ impl CommandData {
 
 // last: 11
 #[repr(C)]
-#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
 pub enum RenderCommandType {
     PushColor = 0,
     PushVec2f = 1,
@@ -174,71 +313,114 @@ pub enum RenderCommandType {
     DrawText = 7,
 }
 
-// last: 5
+// last: 14
 #[repr(C)]
-#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
 pub enum RequestCommandType {
     PushVec2f = 0,
     PushVec2i = 5,
+    PushInt32 = 6,
     SetViewportSize = 1,
+    /// Pops touch position, an optional pointer id, 0 when it's missing, and an optional
+    /// time offset in milliseconds relative to the time of the frame that handles
+    /// the command, i.e. the host time of the last `frame_start`, 0 when it's missing.
     OnTouchStart = 2,
+    /// Same payload as `OnTouchStart`.
     OnTouchEnd = 3,
+    /// Same payload as `OnTouchStart`.
     OnTouchMove = 4,
+    /// Pops modifiers mask and key code.
+    OnKeyDown = 7,
+    /// Pops modifiers mask and key code.
+    OnKeyUp = 8,
+    /// Text is passed in the command data string.
+    OnTextInput = 9,
+    /// Pops modifiers mask and scroll delta, positive `y` scrolls down.
+    OnScroll = 10,
+    /// Pops an optional camera id, the ortho camera when it's missing.
+    CameraFit = 11,
+    /// Pops the world point to center on and an optional camera id,
+    /// the ortho camera when it's missing.
+    CameraGoTo = 12,
+    /// Bookmark name is passed in the command data string. Pops an optional
+    /// camera id, the ortho camera when it's missing.
+    CameraSaveBookmark = 13,
+    /// Bookmark name is passed in the command data string. Pops an optional
+    /// camera id, the ortho camera when it's missing.
+    CameraLoadBookmark = 14,
 }
 
-// last: 2
+// last: 6
 #[repr(C)]
-#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ExecutionCommandType {
     PushVec2f = 0,
     PushInt32 = 2,
+    /// Pops translation and camera id. Deprecated, sent after every
+    /// `UpdateCameraTransform` until the next release.
     UpdateCameraPosition = 1,
+    /// Pops `Cursor` value.
+    SetCursor = 3,
+    /// Pops translation, y axis and x axis of `Transform2D` and camera id.
+    UpdateCameraTransform = 4,
+    /// Pops view port size, view port position in the window and camera id.
+    UpdateCameraViewPort = 5,
+    /// Pops camera id.
+    RemoveCamera = 6,
 }
 
+// last: 9
 #[repr(C)]
-#[derive(Debug, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Cursor {
+    Arrow = 0,
+    Hand = 1,
+    Grab = 2,
+    Grabbing = 3,
+    Crosshair = 4,
+    Text = 5,
+    ResizeHorizontal = 6,
+    ResizeVertical = 7,
+    /// Top left to bottom right.
+    ResizeDiagonalNwse = 8,
+    /// Top right to bottom left.
+    ResizeDiagonalNesw = 9,
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Serialize)]
 pub struct RenderCommand {
     pub command_type: RenderCommandType,
     pub data: CommandData,
 }
 
 #[repr(C)]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct ExecutionCommand {
     pub command_type: ExecutionCommandType,
     pub data: CommandData,
 }
 
 #[repr(C)]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct RequestCommand {
     pub command_type: RequestCommandType,
     pub data: CommandData,
 }
 
+#[derive(Default)]
 pub struct CommandsState {
     pub render_commands: Vec<RenderCommand>,
     pub exec_commands: Vec<ExecutionCommand>,
     pub request_commands: Vec<RequestCommand>,
-}
-
-impl Default for CommandsState {
-    fn default() -> Self {
-        CommandsState {
-            render_commands: Vec::new(),
-            exec_commands: Vec::new(),
-            request_commands: Vec::new(),
-        }
-    }
+    /// Timed block that emitted each of the render commands, matches `render_commands`
+    /// until the render commands optimizer runs.
+    #[cfg(debug_assertions)]
+    pub render_command_sources: Vec<Option<&'static str>>,
 }
 
 pub fn push_render_command(commands_state: &mut CommandsState, command_type: RenderCommandType) {
-    let command = RenderCommand {
-        command_type,
-        data: CommandData::default(),
-    };
-
-    commands_state.render_commands.push(command);
+    push_render_command_data(commands_state, command_type, CommandData::default());
 }
 
 pub fn push_render_command_data(
@@ -248,6 +430,11 @@ pub fn push_render_command_data(
 ) {
     let command = RenderCommand { command_type, data };
     commands_state.render_commands.push(command);
+
+    #[cfg(debug_assertions)]
+    commands_state
+        .render_command_sources
+        .push(crate::debug_services::profile::current_timed_block());
 }
 
 pub fn push_execution_command(
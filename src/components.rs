@@ -1,9 +1,85 @@
-use crate::commands::{Color, Vec2f};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
 
+use crate::commands::{Color, Cursor, Rect, Transform2D, Vec2f};
+use crate::gapi;
+
+#[derive(Clone, Copy, Default, Debug, PartialEq)]
+pub enum GridStyle {
+    #[default]
+    Lines,
+    /// Dots at the line intersections, the axes are still lines.
+    Dots,
+}
+
+/// Every `subdivisions` minor line is a major one. When the minor lines get closer
+/// than `min_spacing` screen pixels the grid switches to the next level, where
+/// the major lines become minor, and the opposite way when zooming in.
+/// The minor lines fade in while their spacing grows to twice of `min_spacing`.
 #[derive(Clone, Copy, Default, Debug)]
 pub struct GridComponent {
+    /// World units between the minor lines of the first level.
     pub step: i32,
-    pub color: Color,
+    pub subdivisions: i32,
+    pub min_spacing: f32,
+    pub minor_color: Color,
+    pub major_color: Color,
+    /// Color of the world x = 0 and y = 0 lines.
+    pub axis_color: Color,
+    pub style: GridStyle,
+}
+
+/// Snapping of the world points to the grid of the camera, see `snapping`.
+/// A point is snapped only when the target is within `threshold` screen pixels,
+/// each axis is snapped separately.
+#[derive(Clone, Copy, Debug)]
+pub struct SnapState {
+    pub is_enabled: bool,
+    pub to_lines: bool,
+    /// Snaps to the centers of the grid cells.
+    pub to_centers: bool,
+    pub threshold: f32,
+}
+
+impl Default for SnapState {
+    fn default() -> Self {
+        SnapState {
+            is_enabled: true,
+            to_lines: true,
+            to_centers: false,
+            threshold: 8.0,
+        }
+    }
+}
+
+/// Rulers along the top and the left edges of the view port of `camera_id`,
+/// see `rulers`. They are drawn with `gapi::CAMERA_UI` and show the world
+/// coordinates of an unrotated camera.
+#[derive(Clone, Copy, Debug)]
+pub struct RulersState {
+    pub is_enabled: bool,
+    pub camera_id: usize,
+    /// Screen pixels.
+    pub thickness: f32,
+    pub background_color: Color,
+    pub tick_color: Color,
+    pub label_color: Color,
+    /// Color of the pointer position marker.
+    pub marker_color: Color,
+}
+
+impl Default for RulersState {
+    fn default() -> Self {
+        RulersState {
+            is_enabled: true,
+            camera_id: gapi::CAMERA_ORTHO,
+            thickness: 20.0,
+            background_color: Color::rgba(1.0, 1.0, 1.0, 0.9),
+            tick_color: Color::rgba(0.0, 0.0, 0.0, 0.4),
+            label_color: Color::rgba(0.0, 0.0, 0.0, 0.7),
+            marker_color: Color::rgb(1.0, 0.0, 0.0),
+        }
+    }
 }
 
 #[derive(Default, Debug)]
@@ -12,6 +88,16 @@ pub struct ViewPortSize {
     pub height: i32,
 }
 
+impl ViewPortSize {
+    pub fn center(&self) -> Vec2f {
+        self.size() / 2.0
+    }
+
+    pub fn size(&self) -> Vec2f {
+        Vec2f::new(self.width as f32, self.height as f32)
+    }
+}
+
 #[derive(Default, Debug)]
 pub struct WorkAreaComponent {
     pub title: String,
@@ -19,34 +105,518 @@ pub struct WorkAreaComponent {
     pub size: Vec2f,
 }
 
-#[derive(Default, Debug)]
+/// Direction of the world y axis on the screen, the screen y axis points down.
+#[derive(Clone, Copy, Default, Debug, PartialEq)]
+pub enum YAxis {
+    #[default]
+    Down,
+    Up,
+}
+
+/// `pos` is the screen offset of the world origin from the center of the camera
+/// view port, the world is scaled by `zoom` and rotated by `rotation` radians around it.
+#[derive(Clone, Copy, Debug)]
 pub struct Camera2D {
     pub id: usize,
     pub pos: Vec2f,
+    pub zoom: f32,
+    pub rotation: f32,
+    pub y_axis: YAxis,
+}
+
+impl Camera2D {
+    /// World to screen transform, `view_port` is the window rect of the camera.
+    /// Use `CameraRegistry` to convert through the transform the host renders with.
+    pub fn transform(&self, view_port: Rect) -> Transform2D {
+        let mut transform =
+            Transform2D::new(view_port.center() + self.pos, self.rotation, self.zoom);
+
+        if self.y_axis == YAxis::Up {
+            transform.y_axis = transform.y_axis * -1.0;
+        }
+
+        transform
+    }
+
+    pub fn screen_to_world(&self, view_port: Rect, point: Vec2f) -> Vec2f {
+        self.transform(view_port).inverse().apply(point)
+    }
+
+    pub fn world_to_screen(&self, view_port: Rect, point: Vec2f) -> Vec2f {
+        self.transform(view_port).apply(point)
+    }
 }
 
+/// Keeps the pointers centroid and spread from the previous frame,
+/// the gesture is applied as a delta between frames.
 #[derive(Default, Debug)]
 pub struct CameraMovable2D {
     pub is_hot: bool,
+    pub last_centroid: Vec2f,
+    pub last_spread: f32,
+    pub last_pointers_count: usize,
+    pub kinetic_scrolling: KineticScrolling,
+    /// Screen pixels per second the camera keeps gliding with after the release.
+    pub velocity: Vec2f,
+}
+
+/// The glide velocity decays `e` times every `1 / friction` seconds
+/// and stops below `min_velocity`.
+#[derive(Clone, Copy, Debug)]
+pub struct KineticScrolling {
+    pub is_enabled: bool,
+    pub friction: f32,
+    pub min_velocity: f32,
+}
+
+/// Keeps at least `margin` screen pixels of the work area visible. Dragging past
+/// the limits is resisted, the overscroll never reaches `max_overscroll` pixels,
+/// and the camera springs back with `stiffness` after the release.
+#[derive(Clone, Copy, Debug)]
+pub struct CameraBounds2D {
+    pub margin: f32,
+    pub max_overscroll: f32,
+    pub stiffness: f32,
+    /// Camera position at the end of the previous frame.
     pub last_pos: Vec2f,
 }
 
+impl CameraBounds2D {
+    /// Range of `Camera2D.pos` that keeps the margin visible.
+    pub fn limits(
+        &self,
+        camera: &Camera2D,
+        view_port: Rect,
+        work_area_size: Vec2f,
+    ) -> (Vec2f, Vec2f) {
+        // Screen extent of the work area relative to the camera translation.
+        let transform = Transform2D::new(Vec2f::ZERO, camera.rotation, camera.zoom);
+        let work_area = Rect::new(Vec2f::ZERO, work_area_size).corners();
+        let extent = Rect::from_points(&work_area.map(|corner| transform.apply(corner)));
+        let center = view_port.size / 2.0;
+        let view_port = view_port.size;
+
+        let limit = |from: f32, size: f32, view_port: f32, center: f32| {
+            let margin = self.margin.min(size).min(view_port);
+            (
+                margin - from - size - center,
+                view_port - margin - from - center,
+            )
+        };
+
+        let (min_x, max_x) = limit(extent.pos.x, extent.size.x, view_port.x, center.x);
+        let (min_y, max_y) = limit(extent.pos.y, extent.size.y, view_port.y, center.y);
+
+        (Vec2f::new(min_x, min_y), Vec2f::new(max_x, max_y))
+    }
+}
+
+impl Default for CameraBounds2D {
+    fn default() -> Self {
+        CameraBounds2D {
+            margin: 64.0,
+            max_overscroll: 120.0,
+            stiffness: 12.0,
+            last_pos: Vec2f::ZERO,
+        }
+    }
+}
+
+impl Default for KineticScrolling {
+    fn default() -> Self {
+        KineticScrolling {
+            is_enabled: true,
+            friction: 4.0,
+            min_velocity: 10.0,
+        }
+    }
+}
+
+pub const CAMERA_ANIMATION_DURATION: Duration = Duration::from_millis(300);
+
+/// Maps the animation progress from 0 to 1 onto the eased progress.
+#[derive(Clone, Copy, Default, Debug, PartialEq)]
+pub enum Easing {
+    Linear,
+    EaseIn,
+    EaseOut,
+    #[default]
+    EaseInOut,
+}
+
+impl Easing {
+    pub fn from_name(name: &str) -> Option<Easing> {
+        match name {
+            "linear" => Some(Easing::Linear),
+            "ease_in" => Some(Easing::EaseIn),
+            "ease_out" => Some(Easing::EaseOut),
+            "ease_in_out" => Some(Easing::EaseInOut),
+            _ => None,
+        }
+    }
+
+    pub fn apply(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t * t,
+            Easing::EaseOut => 1.0 - (1.0 - t).powi(3),
+            Easing::EaseInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                }
+                else {
+                    1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+                }
+            }
+        }
+    }
+}
+
+/// The animated part of `Camera2D`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CameraPose {
+    pub pos: Vec2f,
+    pub zoom: f32,
+    pub rotation: f32,
+}
+
+impl CameraPose {
+    pub fn of(camera: &Camera2D) -> CameraPose {
+        CameraPose {
+            pos: camera.pos,
+            zoom: camera.zoom,
+            rotation: camera.rotation,
+        }
+    }
+
+    /// Zoom is interpolated geometrically so zooming in and out feel the same.
+    pub fn lerp(&self, to: &CameraPose, t: f32) -> CameraPose {
+        CameraPose {
+            pos: self.pos + (to.pos - self.pos) * t,
+            zoom: self.zoom * (to.zoom / self.zoom).powf(t),
+            rotation: self.rotation + (to.rotation - self.rotation) * t,
+        }
+    }
+
+    pub fn apply(&self, camera: &mut Camera2D) {
+        camera.pos = self.pos;
+        camera.zoom = self.zoom;
+        camera.rotation = self.rotation;
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CameraTarget {
+    /// Fits the whole work area into the view port with the rotation reset.
+    FitWorkArea,
+    /// Moves the world point to the view port center, the zoom and rotation are kept.
+    CenterOn(Vec2f),
+    Pose(CameraPose),
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct CameraTween {
+    pub from: CameraPose,
+    pub to: CameraPose,
+    /// Frame time the tween has started at.
+    pub start: Duration,
+}
+
+/// Set `target` to move the camera there over `duration`, the pose is resolved
+/// into `tween` on the next step. Dragging the camera cancels the animation.
+#[derive(Clone, Copy, Debug)]
+pub struct CameraAnimation2D {
+    pub target: Option<CameraTarget>,
+    pub tween: Option<CameraTween>,
+    pub duration: Duration,
+    pub easing: Easing,
+}
+
+impl Default for CameraAnimation2D {
+    fn default() -> Self {
+        CameraAnimation2D {
+            target: None,
+            tween: None,
+            duration: CAMERA_ANIMATION_DURATION,
+            easing: Easing::default(),
+        }
+    }
+}
+
+/// Named camera poses to animate back to.
+#[derive(Default, Debug)]
+pub struct CameraBookmarks {
+    pub bookmarks: HashMap<String, CameraPose>,
+}
+
+/// Places the entity in the world space of the camera with the same `id`.
 #[derive(Clone, Copy, Debug)]
 pub struct Camera2DPositionListener {
     pub id: usize,
-    pub pos: Vec2f,
 }
 
+/// Marks transient entities that are deleted on every flush.
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Actions;
 
+pub const ACTION_PAN: &str = "pan";
+pub const ACTION_ZOOM: &str = "zoom";
+pub const ACTION_ZOOM_IN: &str = "zoom_in";
+pub const ACTION_ZOOM_OUT: &str = "zoom_out";
+pub const ACTION_RESET_CAMERA: &str = "reset_camera";
+
+/// Spawned together with `Actions` for the current frame, `value` keeps
+/// the scroll delta, drag delta or fling velocity of the input.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Action {
+    pub name: String,
+    pub pos: Vec2f,
+    pub value: Vec2f,
+}
+
+/// Modifiers have to match exactly, so ctrl scroll doesn't trigger plain scroll actions.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum InputTrigger {
+    KeyPressed { key_code: i32, modifiers: i32 },
+    Scroll { modifiers: i32 },
+    Gesture(GestureKind),
+}
+
+#[derive(Clone, Debug)]
+pub struct InputBinding {
+    pub trigger: InputTrigger,
+    pub action: String,
+}
+
+/// Key codes are defined by the host, so only scroll and gestures are bound by default.
+#[derive(Debug)]
+pub struct InputBindings {
+    pub bindings: Vec<InputBinding>,
+}
+
+impl InputBindings {
+    pub fn bind(&mut self, trigger: InputTrigger, action: &str) {
+        self.bindings.push(InputBinding {
+            trigger,
+            action: String::from(action),
+        });
+    }
+
+    pub fn unbind(&mut self, action: &str) {
+        self.bindings.retain(|binding| binding.action != action);
+    }
+}
+
+impl Default for InputBindings {
+    fn default() -> Self {
+        let mut input_bindings = InputBindings {
+            bindings: Vec::new(),
+        };
+
+        input_bindings.bind(InputTrigger::Scroll { modifiers: 0 }, ACTION_PAN);
+        input_bindings.bind(
+            InputTrigger::Scroll {
+                modifiers: KEY_MODIFIER_CTRL,
+            },
+            ACTION_ZOOM,
+        );
+        input_bindings.bind(
+            InputTrigger::Gesture(GestureKind::DoubleTap),
+            ACTION_RESET_CAMERA,
+        );
+
+        input_bindings
+    }
+}
+
 impl Camera2DPositionListener {
     pub fn new(id: usize) -> Camera2DPositionListener {
-        Camera2DPositionListener {
+        Camera2DPositionListener { id }
+    }
+}
+
+/// Window area the camera renders to.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CameraViewPort {
+    /// Follows the window size.
+    Window,
+    Rect(Rect),
+}
+
+impl CameraViewPort {
+    pub fn rect(&self, view_port_size: &ViewPortSize) -> Rect {
+        match self {
+            CameraViewPort::Window => Rect::new(Vec2f::ZERO, view_port_size.size()),
+            CameraViewPort::Rect(rect) => *rect,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct CameraEntry {
+    pub id: usize,
+    pub view_port: CameraViewPort,
+    /// Window rect of `view_port` from the last update.
+    pub view_port_rect: Rect,
+    /// World to screen transform from the last update.
+    pub transform: Transform2D,
+    /// View port rect the host knows about, `None` until it's sent.
+    pub sent_view_port: Option<Rect>,
+}
+
+/// Allocates camera ids, they are never reused. `gapi::CAMERA_UI` and
+/// `gapi::CAMERA_ORTHO` are registered from the start and cover the window.
+///
+/// Converts between the window space, with the origin at the top left corner
+/// and the y axis down, and the world space of a camera. The conversions use
+/// the transforms `camera_system` has sent to the host during the current frame
+/// and return `None` for unknown ids.
+#[derive(Clone, Debug)]
+pub struct CameraRegistry {
+    cameras: Vec<CameraEntry>,
+    removed: Vec<usize>,
+    next_id: usize,
+}
+
+impl CameraRegistry {
+    pub fn create(&mut self, view_port: CameraViewPort) -> usize {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.cameras.push(CameraEntry {
             id,
-            pos: Vec2f::ZERO,
+            view_port,
+            view_port_rect: Rect::ZERO,
+            transform: Transform2D::IDENTITY,
+            sent_view_port: None,
+        });
+
+        id
+    }
+
+    /// Returns false if there is no camera with `id`.
+    pub fn remove(&mut self, id: usize) -> bool {
+        let count = self.cameras.len();
+        self.cameras.retain(|camera| camera.id != id);
+
+        if self.cameras.len() == count {
+            return false;
+        }
+
+        self.removed.push(id);
+        true
+    }
+
+    /// Ids of the removed cameras the host doesn't know about yet.
+    pub fn drain_removed(&mut self) -> Vec<usize> {
+        self.removed.drain(..).collect()
+    }
+
+    pub fn get(&self, id: usize) -> Option<&CameraEntry> {
+        self.cameras.iter().find(|camera| camera.id == id)
+    }
+
+    pub fn get_mut(&mut self, id: usize) -> Option<&mut CameraEntry> {
+        self.cameras.iter_mut().find(|camera| camera.id == id)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &CameraEntry> {
+        self.cameras.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut CameraEntry> {
+        self.cameras.iter_mut()
+    }
+
+    /// Window rect of the camera, the whole window for unknown ids.
+    pub fn view_port_rect(&self, id: usize, view_port_size: &ViewPortSize) -> Rect {
+        self.get(id)
+            .map(|camera| camera.view_port)
+            .unwrap_or(CameraViewPort::Window)
+            .rect(view_port_size)
+    }
+
+    /// Resolves the view port rects of the cameras without `Camera2D`.
+    pub fn update_view_ports(&mut self, view_port_size: &ViewPortSize) {
+        for entry in self.cameras.iter_mut() {
+            entry.view_port_rect = entry.view_port.rect(view_port_size);
         }
     }
+
+    /// Returns the new transform, `None` if `camera` isn't registered.
+    pub fn update_camera(
+        &mut self,
+        camera: &Camera2D,
+        view_port_size: &ViewPortSize,
+    ) -> Option<Transform2D> {
+        let entry = self.get_mut(camera.id)?;
+        entry.view_port_rect = entry.view_port.rect(view_port_size);
+        entry.transform = camera.transform(entry.view_port_rect);
+
+        Some(entry.transform)
+    }
+
+    pub fn screen_to_world(&self, id: usize, point: Vec2f) -> Option<Vec2f> {
+        self.get(id)
+            .map(|camera| camera.transform.inverse().apply(point))
+    }
+
+    pub fn world_to_screen(&self, id: usize, point: Vec2f) -> Option<Vec2f> {
+        self.get(id).map(|camera| camera.transform.apply(point))
+    }
+
+    /// Bounding rect of the converted corners, it's larger than `rect` for rotated cameras.
+    pub fn screen_rect_to_world(&self, id: usize, rect: Rect) -> Option<Rect> {
+        self.get(id)
+            .map(|camera| camera.transform.inverse().apply_rect(rect))
+    }
+
+    /// Bounding rect of the converted corners, it's larger than `rect` for rotated cameras.
+    pub fn world_rect_to_screen(&self, id: usize, rect: Rect) -> Option<Rect> {
+        self.get(id).map(|camera| camera.transform.apply_rect(rect))
+    }
+
+    /// Converts a distance along the screen to the world units.
+    pub fn screen_length_to_world(&self, id: usize, length: f32) -> Option<f32> {
+        self.get(id).map(|camera| length / camera.transform.scale())
+    }
+
+    /// World rect that covers the view port of the camera.
+    pub fn visible_world_rect(&self, id: usize) -> Option<Rect> {
+        let view_port = self.get(id)?.view_port_rect;
+        self.screen_rect_to_world(id, view_port)
+    }
+
+    /// Returns false for unknown ids.
+    pub fn contains_screen_point(&self, id: usize, point: Vec2f) -> bool {
+        self.get(id)
+            .is_some_and(|camera| camera.view_port_rect.contains(point))
+    }
+}
+
+impl Default for CameraRegistry {
+    fn default() -> Self {
+        let mut registry = CameraRegistry {
+            cameras: Vec::new(),
+            removed: Vec::new(),
+            next_id: 0,
+        };
+
+        let ui = registry.create(CameraViewPort::Window);
+        let ortho = registry.create(CameraViewPort::Window);
+        debug_assert_eq!((gapi::CAMERA_UI, gapi::CAMERA_ORTHO), (ui, ortho));
+
+        registry
+    }
+}
+
+/// Pickable area in the world space of the camera from `Camera2DPositionListener`,
+/// entities with the greater `z_order` are on top.
+#[derive(Clone, Copy, Debug)]
+pub struct Bounds {
+    pub rect: Rect,
+    pub z_order: i32,
 }
 
 #[derive(Debug, PartialEq)]
@@ -57,6 +627,35 @@ pub enum Touch {
     End,
 }
 
+/// The pointer that paused longer than this before the release doesn't keep its velocity.
+pub const RELEASE_VELOCITY_MAX_PAUSE: Duration = Duration::from_millis(100);
+
+#[derive(Clone, Copy, Debug)]
+pub struct TouchPointer {
+    pub id: i32,
+    pub start: Vec2f,
+    pub current: Vec2f,
+    /// Timestamp of the `current` position.
+    pub time: Duration,
+    /// Screen pixels per second between the last two samples.
+    pub velocity: Vec2f,
+}
+
+impl TouchPointer {
+    fn sample(&mut self, touch: Vec2f, time: Duration) {
+        let elapsed = time.saturating_sub(self.time).as_secs_f32();
+
+        if elapsed > 0.0 {
+            self.velocity = (touch - self.current) / elapsed;
+            self.time = time;
+        }
+
+        self.current = touch;
+    }
+}
+
+/// `pointers` keeps every pointer that is currently down, `touch`, `touch_start`
+/// and `touch_current` follow the primary pointer only - the first one that went down.
 #[derive(Debug)]
 pub struct TouchState {
     pub last_touch: Touch,
@@ -64,16 +663,247 @@ pub struct TouchState {
     pub pos: Vec2f,
     pub touch_start: Vec2f,
     pub touch_current: Vec2f,
+    pub pointers: Vec<TouchPointer>,
+    pub primary_pointer: Option<i32>,
+    /// Velocity of the last released pointer.
+    pub release_velocity: Vec2f,
 }
 
+impl TouchState {
+    pub fn on_touch_start(&mut self, pointer_id: i32, touch: Vec2f, time: Duration) {
+        // NOTE: The host may lose the end of a touch, then the pointer just starts over.
+        self.pointers.retain(|pointer| pointer.id != pointer_id);
+        self.pointers.push(TouchPointer {
+            id: pointer_id,
+            start: touch,
+            current: touch,
+            time,
+            velocity: Vec2f::ZERO,
+        });
+
+        if self.primary_pointer.is_none() || self.primary_pointer == Some(pointer_id) {
+            self.primary_pointer = Some(pointer_id);
+            // Touch screens don't hover, so `pos` has to follow the touch as well.
+            self.pos = touch;
+            self.touch = Touch::Start;
+            self.touch_start = touch;
+            self.touch_current = touch;
+        }
+    }
+
+    pub fn on_touch_move(&mut self, pointer_id: i32, touch: Vec2f, time: Duration) {
+        if let Some(pointer) = self.pointer_mut(pointer_id) {
+            pointer.sample(touch, time);
+        }
+
+        if self.primary_pointer.is_none() {
+            self.pos = touch;
+        }
+        else if self.primary_pointer == Some(pointer_id) {
+            self.pos = touch;
+            self.touch = Touch::Move;
+            self.touch_current = touch;
+        }
+    }
+
+    pub fn on_touch_end(&mut self, pointer_id: i32, touch: Vec2f, time: Duration) {
+        if let Some(pointer) = self.pointer_mut(pointer_id) {
+            // Hosts usually repeat the last position on release, it isn't a stop.
+            if touch != pointer.current {
+                pointer.sample(touch, time);
+            }
+
+            self.release_velocity =
+                if time.saturating_sub(pointer.time) > RELEASE_VELOCITY_MAX_PAUSE {
+                    Vec2f::ZERO
+                }
+                else {
+                    pointer.velocity
+                };
+        }
+
+        self.pointers.retain(|pointer| pointer.id != pointer_id);
+
+        if self.primary_pointer == Some(pointer_id) {
+            self.primary_pointer = None;
+            self.touch = Touch::End;
+            self.touch_current = touch;
+        }
+    }
+
+    pub fn pointer(&self, pointer_id: i32) -> Option<&TouchPointer> {
+        self.pointers
+            .iter()
+            .find(|pointer| pointer.id == pointer_id)
+    }
+
+    pub fn pointer_mut(&mut self, pointer_id: i32) -> Option<&mut TouchPointer> {
+        self.pointers
+            .iter_mut()
+            .find(|pointer| pointer.id == pointer_id)
+    }
+
+    /// Average position of the active pointers.
+    pub fn pointers_centroid(&self) -> Vec2f {
+        if self.pointers.is_empty() {
+            return Vec2f::ZERO;
+        }
+
+        let sum = self
+            .pointers
+            .iter()
+            .fold(Vec2f::ZERO, |sum, pointer| sum + pointer.current);
+
+        sum / self.pointers.len() as f32
+    }
+
+    /// Average distance from the active pointers to `centroid`.
+    pub fn pointers_spread(&self, centroid: Vec2f) -> f32 {
+        if self.pointers.is_empty() {
+            return 0.0;
+        }
+
+        let sum: f32 = self
+            .pointers
+            .iter()
+            .map(|pointer| (pointer.current - centroid).length())
+            .sum();
+
+        sum / self.pointers.len() as f32
+    }
+}
+
+pub const KEY_MODIFIER_SHIFT: i32 = 1;
+pub const KEY_MODIFIER_CTRL: i32 = 1 << 1;
+pub const KEY_MODIFIER_ALT: i32 = 1 << 2;
+pub const KEY_MODIFIER_META: i32 = 1 << 3;
+
+/// Key codes are defined by the host, `pressed`, `released` and `text_input`
+/// only keep what happened during the current frame.
+#[derive(Debug, Default)]
+pub struct KeyboardState {
+    pub keys_down: Vec<i32>,
+    pub pressed: Vec<i32>,
+    pub released: Vec<i32>,
+    pub modifiers: i32,
+    pub text_input: String,
+}
+
+impl KeyboardState {
+    pub fn is_key_down(&self, key_code: i32) -> bool {
+        self.keys_down.contains(&key_code)
+    }
+
+    pub fn is_key_pressed(&self, key_code: i32) -> bool {
+        self.pressed.contains(&key_code)
+    }
+
+    pub fn is_key_released(&self, key_code: i32) -> bool {
+        self.released.contains(&key_code)
+    }
+
+    pub fn has_modifiers(&self, modifiers: i32) -> bool {
+        self.modifiers & modifiers == modifiers
+    }
+
+    pub fn reset(&mut self) {
+        self.pressed.clear();
+        self.released.clear();
+        self.text_input.clear();
+    }
+}
+
+/// Scroll delta accumulated during the current frame, positive `y` scrolls down.
+#[derive(Debug, Default)]
+pub struct ScrollState {
+    pub delta: Vec2f,
+    pub modifiers: i32,
+}
+
+impl ScrollState {
+    pub fn has_modifiers(&self, modifiers: i32) -> bool {
+        self.modifiers & modifiers == modifiers
+    }
+
+    pub fn reset(&mut self) {
+        self.delta = Vec2f::ZERO;
+    }
+}
+
+/// Input layers ordered from the top to the bottom of the stack.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum InputLayer {
+    DebugOverlay,
+    ModalUi,
+    Tools,
+    Canvas,
+}
+
+/// What a layer does with the pointer event it was hit by.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PointerEvent {
+    /// The layers below don't receive the event.
+    Consume,
+    /// The layers below receive the event as if the layer wasn't hit.
+    PassThrough,
+}
+
+/// Layers are stepped from the top to the bottom, a layer receives the pointer
+/// unless a layer above has consumed it or another layer has captured it.
+/// The capture lasts until all of the pointers are up.
 #[derive(Debug, Default)]
 pub struct LayersState {
-    pub ui_layer_is_hot: bool,
+    consumed_by: Option<InputLayer>,
+    captured_by: Option<InputLayer>,
 }
 
 impl LayersState {
-    pub fn reset(&mut self) {
-        self.ui_layer_is_hot = false;
+    /// Has to be called before the layers are stepped.
+    pub fn begin_step(&mut self, touch_state: &TouchState) {
+        self.consumed_by = None;
+
+        if touch_state.pointers.is_empty() {
+            self.captured_by = None;
+        }
+    }
+
+    pub fn hit(&mut self, layer: InputLayer, event: PointerEvent) {
+        if event == PointerEvent::Consume && !matches!(self.consumed_by, Some(top) if top <= layer)
+        {
+            self.consumed_by = Some(layer);
+        }
+    }
+
+    /// Returns false if the pointer is already captured by another layer.
+    pub fn capture(&mut self, layer: InputLayer) -> bool {
+        if self.captured_by.is_none() {
+            self.captured_by = Some(layer);
+        }
+
+        self.captured_by == Some(layer)
+    }
+
+    pub fn release(&mut self, layer: InputLayer) {
+        if self.captured_by == Some(layer) {
+            self.captured_by = None;
+        }
+    }
+
+    pub fn receives_pointer(&self, layer: InputLayer) -> bool {
+        match self.captured_by {
+            Some(owner) => owner == layer,
+            None => !matches!(self.consumed_by, Some(top) if top < layer),
+        }
+    }
+
+    pub fn captured_by(&self) -> Option<InputLayer> {
+        self.captured_by
+    }
+
+    /// The layer that has captured the pointer, or the topmost layer that has
+    /// consumed it during the current step.
+    pub fn pointer_owner(&self) -> Option<InputLayer> {
+        self.captured_by.or(self.consumed_by)
     }
 }
 
@@ -85,6 +915,405 @@ impl Default for TouchState {
             pos: Vec2f::ZERO,
             touch_start: Vec2f::ZERO,
             touch_current: Vec2f::ZERO,
+            pointers: Vec::new(),
+            primary_pointer: None,
+            release_velocity: Vec2f::ZERO,
         }
     }
 }
+
+/// Time since the world was created, sampled at the frame start.
+#[derive(Debug)]
+pub struct FrameClock {
+    pub start: Instant,
+    pub time: Duration,
+    pub last_time: Duration,
+}
+
+impl FrameClock {
+    pub fn tick(&mut self) {
+        self.set_time(self.start.elapsed());
+    }
+
+    pub fn set_time(&mut self, time: Duration) {
+        self.last_time = self.time;
+        self.time = time;
+    }
+
+    /// Time between the last two frames.
+    pub fn delta(&self) -> Duration {
+        self.time.saturating_sub(self.last_time)
+    }
+}
+
+impl Default for FrameClock {
+    fn default() -> Self {
+        FrameClock {
+            start: Instant::now(),
+            time: Duration::default(),
+            last_time: Duration::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Gesture {
+    Tap {
+        pos: Vec2f,
+    },
+    /// The first tap is reported as `Tap` as well.
+    DoubleTap {
+        pos: Vec2f,
+    },
+    LongPress {
+        pos: Vec2f,
+    },
+    DragStart {
+        start: Vec2f,
+        pos: Vec2f,
+    },
+    Drag {
+        pos: Vec2f,
+        delta: Vec2f,
+    },
+    DragEnd {
+        pos: Vec2f,
+    },
+    /// Follows `DragEnd` when the pointer was released while moving,
+    /// `velocity` is in pixels per second.
+    Fling {
+        pos: Vec2f,
+        velocity: Vec2f,
+    },
+}
+
+impl Gesture {
+    pub fn kind(&self) -> GestureKind {
+        match self {
+            Gesture::Tap { .. } => GestureKind::Tap,
+            Gesture::DoubleTap { .. } => GestureKind::DoubleTap,
+            Gesture::LongPress { .. } => GestureKind::LongPress,
+            Gesture::DragStart { .. } => GestureKind::DragStart,
+            Gesture::Drag { .. } => GestureKind::Drag,
+            Gesture::DragEnd { .. } => GestureKind::DragEnd,
+            Gesture::Fling { .. } => GestureKind::Fling,
+        }
+    }
+
+    pub fn pos(&self) -> Vec2f {
+        match *self {
+            Gesture::Tap { pos }
+            | Gesture::DoubleTap { pos }
+            | Gesture::LongPress { pos }
+            | Gesture::DragStart { pos, .. }
+            | Gesture::Drag { pos, .. }
+            | Gesture::DragEnd { pos }
+            | Gesture::Fling { pos, .. } => pos,
+        }
+    }
+
+    /// Drag delta or fling velocity, zero for the rest.
+    pub fn value(&self) -> Vec2f {
+        match *self {
+            Gesture::Drag { delta, .. } => delta,
+            Gesture::Fling { velocity, .. } => velocity,
+            _ => Vec2f::ZERO,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GestureKind {
+    Tap,
+    DoubleTap,
+    LongPress,
+    DragStart,
+    Drag,
+    DragEnd,
+    Fling,
+}
+
+/// Distances are in pixels.
+#[derive(Debug, Clone, Copy)]
+pub struct GestureConfig {
+    pub drag_threshold: f32,
+    pub tap_max_duration: Duration,
+    pub double_tap_max_interval: Duration,
+    pub double_tap_max_distance: f32,
+    pub long_press_duration: Duration,
+    /// Pixels per second.
+    pub fling_min_velocity: f32,
+    /// Pointer that stood still longer than this before the release doesn't fling.
+    pub fling_max_pause: Duration,
+}
+
+impl Default for GestureConfig {
+    fn default() -> Self {
+        GestureConfig {
+            drag_threshold: 8.0,
+            tap_max_duration: Duration::from_millis(300),
+            double_tap_max_interval: Duration::from_millis(300),
+            double_tap_max_distance: 24.0,
+            long_press_duration: Duration::from_millis(500),
+            fling_min_velocity: 400.0,
+            fling_max_pause: Duration::from_millis(100),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct GesturePress {
+    pub start: Vec2f,
+    pub start_time: Duration,
+    pub last_pos: Vec2f,
+    pub last_time: Duration,
+    pub velocity: Vec2f,
+    pub is_dragging: bool,
+    pub is_long_pressed: bool,
+    /// Set when more pointers went down, the press doesn't produce gestures anymore.
+    pub is_cancelled: bool,
+}
+
+impl GesturePress {
+    pub fn new(start: Vec2f, time: Duration) -> GesturePress {
+        GesturePress {
+            start,
+            start_time: time,
+            last_pos: start,
+            last_time: time,
+            velocity: Vec2f::ZERO,
+            is_dragging: false,
+            is_long_pressed: false,
+            is_cancelled: false,
+        }
+    }
+}
+
+/// Gestures of the primary pointer, `gestures` only keeps the ones
+/// recognized during the current step.
+#[derive(Debug, Default)]
+pub struct GestureState {
+    pub config: GestureConfig,
+    pub gestures: Vec<Gesture>,
+    pub press: Option<GesturePress>,
+    pub last_tap: Option<(Vec2f, Duration)>,
+}
+
+/// `is_entered` and `is_left` only last for the step they happened in.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct HoverState {
+    pub is_hovered: bool,
+    pub is_entered: bool,
+    pub is_left: bool,
+}
+
+impl HoverState {
+    pub fn update(&mut self, is_hovered: bool) {
+        self.is_entered = is_hovered && !self.is_hovered;
+        self.is_left = !is_hovered && self.is_hovered;
+        self.is_hovered = is_hovered;
+    }
+}
+
+/// Hovered through `Bounds`, only the topmost entity under the pointer is hovered.
+#[derive(Clone, Copy, Debug)]
+pub struct Hoverable {
+    pub cursor: Cursor,
+    pub hover: HoverState,
+}
+
+impl Hoverable {
+    pub fn new(cursor: Cursor) -> Hoverable {
+        Hoverable {
+            cursor,
+            hover: HoverState::default(),
+        }
+    }
+}
+
+/// `cursor` is gathered during the step, the host only receives it when it changes.
+#[derive(Debug)]
+pub struct CursorState {
+    pub cursor: Cursor,
+    pub last_cursor: Option<Cursor>,
+}
+
+impl CursorState {
+    pub fn reset(&mut self) {
+        // Empty canvas can be dragged.
+        self.cursor = Cursor::Grab;
+    }
+}
+
+impl Default for CursorState {
+    fn default() -> Self {
+        CursorState {
+            cursor: Cursor::Grab,
+            last_cursor: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::{Rect, Vec2f};
+    use crate::components::{
+        Camera2D, CameraRegistry, InputLayer, KeyboardState, LayersState, ViewPortSize, YAxis,
+        KEY_MODIFIER_CTRL,
+    };
+    use crate::debug_services::state::DebugState;
+    use crate::errors::StatusCode;
+    use crate::test_utils::TestWorld;
+
+    #[test]
+    fn keyboard_input() {
+        let mut world = TestWorld::new();
+        let key_a = 65;
+
+        crate::push_on_key_down_request_command(world.state(), key_a, KEY_MODIFIER_CTRL);
+        crate::push_on_key_down_request_command(world.state(), key_a, KEY_MODIFIER_CTRL);
+        crate::push_on_text_input_request_command(world.state(), "ab");
+        crate::push_on_text_input_request_command(world.state(), "в");
+
+        assert_eq!(StatusCode::Ok, unsafe { crate::step(world.handle) });
+
+        {
+            let state = world.state();
+            let keyboard_state = state.world.resources.get::<KeyboardState>().unwrap();
+
+            assert!(keyboard_state.is_key_down(key_a));
+            assert!(keyboard_state.is_key_pressed(key_a));
+            assert!(keyboard_state.has_modifiers(KEY_MODIFIER_CTRL));
+            assert_eq!(vec![key_a], keyboard_state.pressed);
+            assert_eq!("abв", keyboard_state.text_input);
+        }
+
+        world.end_frame();
+        crate::push_on_key_up_request_command(world.state(), key_a, 0);
+
+        {
+            let state = world.state();
+            let keyboard_state = state.world.resources.get::<KeyboardState>().unwrap();
+
+            assert!(keyboard_state.is_key_down(key_a));
+            assert!(!keyboard_state.is_key_pressed(key_a));
+            assert!(keyboard_state.text_input.is_empty());
+        }
+
+        assert_eq!(StatusCode::Ok, unsafe { crate::step(world.handle) });
+
+        let state = world.state();
+        let keyboard_state = state.world.resources.get::<KeyboardState>().unwrap();
+
+        assert!(!keyboard_state.is_key_down(key_a));
+        assert!(keyboard_state.is_key_released(key_a));
+        assert!(!keyboard_state.has_modifiers(KEY_MODIFIER_CTRL));
+    }
+
+    fn pointer_owner(world: &mut TestWorld) -> Option<InputLayer> {
+        let state = world.state();
+        let layers_state = state.world.resources.get::<LayersState>().unwrap();
+        layers_state.pointer_owner()
+    }
+
+    #[test]
+    fn pointer_capture() {
+        let mut world = TestWorld::with_view_port(640, 480);
+
+        {
+            let state = world.state();
+            let mut debug_state = state.world.resources.get_mut::<DebugState>().unwrap();
+            debug_state.variables.bounds = Rect::new(Vec2f::ZERO, Vec2f::new(100.0, 40.0));
+        }
+
+        // The canvas keeps the pointer while it is dragged over the debug overlay.
+        crate::push_on_touch_start_request_command(world.state(), 0, 0, Vec2f::new(400.0, 300.0));
+        world.step();
+        assert_eq!(Some(InputLayer::Canvas), pointer_owner(&mut world));
+
+        crate::push_on_touch_move_request_command(world.state(), 0, 0, Vec2f::new(50.0, 20.0));
+        world.step();
+        assert_eq!(Some(InputLayer::Canvas), pointer_owner(&mut world));
+        assert_eq!((Vec2f::new(-670.0, -520.0), 1.0), world.camera_transform());
+
+        crate::push_on_touch_end_request_command(world.state(), 0, 0, Vec2f::new(50.0, 20.0));
+        world.step();
+        assert_eq!(Some(InputLayer::DebugOverlay), pointer_owner(&mut world));
+
+        // And the other way around.
+        crate::push_on_touch_start_request_command(world.state(), 0, 0, Vec2f::new(50.0, 20.0));
+        world.step();
+        crate::push_on_touch_move_request_command(world.state(), 0, 0, Vec2f::new(400.0, 300.0));
+        world.step();
+        assert_eq!(Some(InputLayer::DebugOverlay), pointer_owner(&mut world));
+        assert_eq!((Vec2f::new(-670.0, -520.0), 1.0), world.camera_transform());
+
+        crate::push_on_touch_end_request_command(world.state(), 0, 0, Vec2f::new(400.0, 300.0));
+        world.step();
+        assert_eq!(None, pointer_owner(&mut world));
+    }
+
+    #[test]
+    fn camera_conversions() {
+        let view_port_size = ViewPortSize {
+            width: 640,
+            height: 480,
+        };
+        let mut camera = Camera2D {
+            id: crate::gapi::CAMERA_ORTHO,
+            pos: Vec2f::new(-100.0, 0.0),
+            zoom: 2.0,
+            rotation: 0.0,
+            y_axis: YAxis::Down,
+        };
+        let mut camera_registry = CameraRegistry::default();
+        camera_registry.update_camera(&camera, &view_port_size);
+
+        let id = crate::gapi::CAMERA_ORTHO;
+        let world = camera_registry
+            .screen_to_world(id, Vec2f::new(420.0, 260.0))
+            .unwrap();
+
+        assert_eq!(Vec2f::new(100.0, 10.0), world);
+        assert_eq!(
+            Some(Vec2f::new(420.0, 260.0)),
+            camera_registry.world_to_screen(id, world)
+        );
+        assert_eq!(Some(16.0), camera_registry.screen_length_to_world(id, 32.0));
+
+        let rect = Rect::new(Vec2f::new(10.0, 20.0), Vec2f::new(30.0, 40.0));
+        let screen_rect = camera_registry.world_rect_to_screen(id, rect).unwrap();
+
+        assert_eq!(
+            Rect::new(Vec2f::new(240.0, 280.0), Vec2f::new(60.0, 80.0)),
+            screen_rect
+        );
+        assert_eq!(
+            Some(rect),
+            camera_registry.screen_rect_to_world(id, screen_rect)
+        );
+        assert_eq!(
+            Some(Rect::new(
+                Vec2f::new(-110.0, -120.0),
+                Vec2f::new(320.0, 240.0)
+            )),
+            camera_registry.visible_world_rect(id)
+        );
+
+        // With the y axis up the world points above the origin are above it on the screen.
+        camera.y_axis = YAxis::Up;
+        camera_registry.update_camera(&camera, &view_port_size);
+
+        assert_eq!(
+            Some(Vec2f::new(420.0, 220.0)),
+            camera_registry.world_to_screen(id, Vec2f::new(100.0, 10.0))
+        );
+        assert_eq!(
+            Some(Vec2f::new(100.0, 10.0)),
+            camera_registry.screen_to_world(id, Vec2f::new(420.0, 220.0))
+        );
+
+        assert_eq!(None, camera_registry.screen_to_world(7, Vec2f::ZERO));
+        assert!(!camera_registry.contains_screen_point(7, Vec2f::ZERO));
+    }
+}
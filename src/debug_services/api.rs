@@ -1,45 +1,84 @@
-use crate::commands::CommandsState;
-use crate::components::{LayersState, TouchState, ViewPortSize};
+use legion::prelude::*;
+
+use crate::commands;
+use crate::components::{CursorState, LayersState, TouchState, ViewPortSize};
 use crate::debug_services::commands_registry;
 use crate::debug_services::profile;
-use crate::debug_services::profile::PROFILE_STATE;
+use crate::debug_services::profile::ProfileState;
 use crate::debug_services::render;
-use crate::debug_services::state::DEBUG_STATE;
+use crate::debug_services::state::DebugState;
 use crate::debug_services::step;
-use crate::render_state::RENDER_STATE;
+use crate::memory::MemoryState;
+use crate::render_state::RenderState;
 
 pub use crate::debug_services::commands::*;
 
-pub fn debug_frame_end() {
-    let profile_state = &mut PROFILE_STATE.lock().expect("failed to get profile state");
+pub fn debug_frame_end(profile_state: &mut ProfileState) {
     profile::frame_end(profile_state);
 }
 
-pub fn debug_frame_start() {
-    let profile_state = &mut PROFILE_STATE.lock().expect("failed to get profile state");
+pub fn debug_frame_start(profile_state: &mut ProfileState) {
     profile::frame_start(profile_state);
 }
 
-pub fn init() {
-    let debug_state = &mut DEBUG_STATE.lock().expect("failed to get debug state");
-    commands_registry::init(debug_state);
+pub fn init(resources: &mut Resources) {
+    let mut debug_state = DebugState::default();
+    commands_registry::init(&mut debug_state);
+
+    resources.insert(debug_state);
+    resources.insert(ProfileState::default());
 }
 
-pub fn render_pass(commands_state: &mut CommandsState, view_port: &ViewPortSize) {
-    let debug_state = &mut DEBUG_STATE.lock().expect("failed to get debug state");
-    let render_state = &mut RENDER_STATE.lock().expect("failed to get render state");
-    let profile_state = &mut PROFILE_STATE.lock().expect("failed to get profile state");
+pub fn render_pass(resources: &Resources, memory_state: &MemoryState) {
+    let debug_state = &mut resources
+        .get_mut::<DebugState>()
+        .expect("failed to get debug state");
+
+    let render_state = &mut resources
+        .get_mut::<RenderState>()
+        .expect("failed to get render state");
+
+    let profile_state = &resources
+        .get::<ProfileState>()
+        .expect("failed to get profile state");
+
+    let _block =
+        crate::debug_services::timed_block!(profile_state.sink.clone(), "debug_render_pass");
+
+    let commands_state = &mut resources
+        .get_mut::<commands::CommandsState>()
+        .expect("failed to get commands state");
+
+    let view_port = &resources
+        .get::<ViewPortSize>()
+        .expect("failed to get view port size");
 
     render::render(
         debug_state,
         render_state,
         profile_state,
+        memory_state,
         commands_state,
         view_port,
     );
 }
 
-pub fn ui_step_pass(touch_state: &TouchState, layers_state: &mut LayersState) {
-    let debug_state = &mut DEBUG_STATE.lock().expect("failed to get debug state");
-    step::step(debug_state, touch_state, layers_state);
+pub fn ui_step_pass(resources: &Resources) {
+    let debug_state = &mut resources
+        .get_mut::<DebugState>()
+        .expect("failed to get debug state");
+
+    let touch_state = &resources
+        .get::<TouchState>()
+        .expect("failed to get touch state");
+
+    let layers_state = &mut resources
+        .get_mut::<LayersState>()
+        .expect("failed to get layers state");
+
+    let cursor_state = &mut resources
+        .get_mut::<CursorState>()
+        .expect("failed to get cursor state");
+
+    step::step(debug_state, touch_state, layers_state, cursor_state);
 }
@@ -1,10 +1,10 @@
+use legion::prelude::*;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 
 use std::collections::HashMap;
-use std::sync::MutexGuard;
 
-use crate::debug_services::state::{DebugState, DEBUG_STATE};
+use crate::debug_services::state::DebugState;
 
 pub const COMMANDS_HISTORY_CAPACITY: usize = 100;
 
@@ -42,7 +42,7 @@ pub enum Token<'a> {
 pub struct Command {
     pub namespace: String,
     pub name: String,
-    pub executor: fn(&mut MutexGuard<DebugState>, &[CommandArgument]) -> Result<(), String>,
+    pub executor: fn(&mut World, &[CommandArgument]) -> Result<(), String>,
 }
 
 #[derive(PartialEq, Debug)]
@@ -58,11 +58,7 @@ pub struct CommandRegistryEntry {
     pub _desc: &'static str,
 }
 
-pub fn register_command(
-    debug_state: &mut MutexGuard<DebugState>,
-    desc: &'static str,
-    command: Command,
-) {
+pub fn register_command(debug_state: &mut DebugState, desc: &'static str, command: Command) {
     debug_state.commands.registry.push(CommandRegistryEntry {
         namespace: command.namespace.clone(),
         name: command.name.clone(),
@@ -76,11 +72,18 @@ pub fn register_command(
     );
 }
 
-pub fn execute_command(command: &str) -> Result<(), String> {
-    let debug_state = &mut DEBUG_STATE.lock().expect("failed to get debug state");
-    debug_state.commands.history.push(String::from(command));
-    let request = parse_command(command)?;
-    execute_command_request(debug_state, &request)
+pub fn execute_command(world: &mut World, command: &str) -> Result<(), String> {
+    let request = {
+        let mut debug_state = world
+            .resources
+            .get_mut::<DebugState>()
+            .expect("failed to get debug state");
+
+        debug_state.commands.history.push(String::from(command));
+        parse_command(command)?
+    };
+
+    execute_command_request(world, &request)
 }
 
 fn parse_command(command: &str) -> Result<CommandRequest, String> {
@@ -118,7 +121,7 @@ fn parse_command(command: &str) -> Result<CommandRequest, String> {
 fn tokenize<'a>(command: &'a str) -> Vec<Token<'a>> {
     let mut tokens = Vec::new();
 
-    let re = Regex::new(r###"(?P<bool>true|false)|("(?P<string>[^"]*)")|(?P<id>[a-zA-Z_][a-zA-Z:0-9_-]+)|(?P<number>[0-9]+(\.[0-9]+)?)"###).unwrap();
+    let re = Regex::new(r###"(?P<bool>true|false)|("(?P<string>[^"]*)")|(?P<id>[a-zA-Z_][a-zA-Z:0-9_-]+)|(?P<number>-?[0-9]+(\.[0-9]+)?)"###).unwrap();
 
     for cap in re.captures_iter(command) {
         if let Some(m) = cap.name("id") {
@@ -138,15 +141,18 @@ fn tokenize<'a>(command: &'a str) -> Vec<Token<'a>> {
     tokens
 }
 
-fn execute_command_request(
-    debug_state: &mut MutexGuard<DebugState>,
-    request: &CommandRequest,
-) -> Result<(), String> {
-    match debug_state.commands.index.get(&request.command) {
-        Some(command) => {
-            let executor = command.executor;
-            executor(debug_state, &request.arguments)
-        }
+fn execute_command_request(world: &mut World, request: &CommandRequest) -> Result<(), String> {
+    let executor = world
+        .resources
+        .get::<DebugState>()
+        .expect("failed to get debug state")
+        .commands
+        .index
+        .get(&request.command)
+        .map(|command| command.executor);
+
+    match executor {
+        Some(executor) => executor(world, &request.arguments),
         None => Err(format!("Command '{}' not found", request.command)),
     }
 }
@@ -163,11 +169,31 @@ pub fn require(cond: bool, msg: &str) -> Result<(), String> {
 #[cfg(test)]
 mod tests {
     use crate::debug_services::commands;
-    use std::sync::MutexGuard;
+    use crate::debug_services::state::DebugState;
+    use legion::prelude::*;
+
+    fn create_world() -> World {
+        let mut world = Universe::new().create_world();
+        let mut debug_state = DebugState::default();
+
+        commands::register_command(
+            &mut debug_state,
+            "Test commands",
+            commands::Command {
+                namespace: String::from("math"),
+                name: String::from("sum"),
+                executor: sum_command,
+            },
+        );
+
+        world.resources.insert(debug_state);
+        world
+    }
 
     #[test]
     fn tokenize() {
-        let tokens = commands::tokenize("greet::hello test 12 55.9 \"Hello World!\" false true");
+        let tokens =
+            commands::tokenize("greet::hello test 12 55.9 -4.5 \"Hello World!\" false true");
         assert_eq!(
             tokens,
             vec![
@@ -175,6 +201,7 @@ mod tests {
                 commands::Token::Id("test"),
                 commands::Token::Number(12.0),
                 commands::Token::Number(55.9),
+                commands::Token::Number(-4.5),
                 commands::Token::String("Hello World!"),
                 commands::Token::Bool(false),
                 commands::Token::Bool(true),
@@ -244,76 +271,31 @@ mod tests {
 
     #[test]
     fn execute_command() {
-        {
-            let debug_state = &mut commands::DEBUG_STATE
-                .lock()
-                .expect("failed to get debug state");
-
-            commands::register_command(
-                debug_state,
-                "Test commands",
-                commands::Command {
-                    namespace: String::from("math"),
-                    name: String::from("sum"),
-                    executor: sum_command,
-                },
-            );
-        }
-        assert_eq!(true, commands::execute_command("math::sum 2 2").is_ok());
+        let mut world = create_world();
+        assert!(commands::execute_command(&mut world, "math::sum 2 2").is_ok());
     }
 
     #[test]
     fn execute_command_failed_type() {
-        {
-            let debug_state = &mut commands::DEBUG_STATE
-                .lock()
-                .expect("failed to get debug state");
-
-            commands::register_command(
-                debug_state,
-                "Test commands",
-                commands::Command {
-                    namespace: String::from("math"),
-                    name: String::from("sum"),
-                    executor: sum_command,
-                },
-            );
-        }
+        let mut world = create_world();
 
-        let res = commands::execute_command("math::sum 2 \"2\"");
+        let res = commands::execute_command(&mut world, "math::sum 2 \"2\"");
 
-        assert_eq!(true, res.is_err());
+        assert!(res.is_err());
         assert_eq!("second argument should be number", res.err().unwrap());
     }
 
     #[test]
     fn execute_command_failed() {
-        {
-            let debug_state = &mut commands::DEBUG_STATE
-                .lock()
-                .expect("failed to get debug state");
-
-            commands::register_command(
-                debug_state,
-                "Test commands",
-                commands::Command {
-                    namespace: String::from("math"),
-                    name: String::from("sum"),
-                    executor: sum_command,
-                },
-            );
-        }
+        let mut world = create_world();
 
-        let res = commands::execute_command("math::sum 2");
+        let res = commands::execute_command(&mut world, "math::sum 2");
 
-        assert_eq!(true, res.is_err());
+        assert!(res.is_err());
         assert_eq!("bad arguments length", res.err().unwrap());
     }
 
-    fn sum_command(
-        _: &mut MutexGuard<commands::DebugState>,
-        arguments: &[commands::CommandArgument],
-    ) -> Result<(), String> {
+    fn sum_command(_: &mut World, arguments: &[commands::CommandArgument]) -> Result<(), String> {
         commands::require(arguments.len() == 2, "bad arguments length")?;
 
         let a = match arguments[0] {
@@ -1,10 +1,24 @@
-use std::sync::MutexGuard;
+use legion::prelude::*;
+use legion::resource::FetchMut;
 
+use std::time::Duration;
+
+use crate::commands::Vec2f;
+use crate::components::{
+    CameraAnimation2D, CameraTarget, Easing, GridComponent, GridStyle, InputBindings, InputTrigger,
+    RulersState, SnapState,
+};
 use crate::debug_services::commands::*;
 use crate::debug_services::profile;
+use crate::debug_services::profile::ProfileState;
 use crate::debug_services::state::DebugState;
+use crate::gapi;
+use crate::render_optimizer::RenderOptimizerState;
+use crate::systems::camera_animation::{
+    animate_camera, load_camera_bookmark, save_camera_bookmark,
+};
 
-pub fn init(debug_state: &mut MutexGuard<DebugState>) {
+pub fn init(debug_state: &mut DebugState) {
     register_command(
         debug_state,
         "Update snapshot interval",
@@ -14,10 +28,140 @@ pub fn init(debug_state: &mut MutexGuard<DebugState>) {
             executor: set_snapshot_interval_command,
         },
     );
+
+    register_command(
+        debug_state,
+        "Bind key with modifiers to action",
+        Command {
+            namespace: String::from("input"),
+            name: String::from("bind_key"),
+            executor: bind_key_command,
+        },
+    );
+
+    register_command(
+        debug_state,
+        "Remove all bindings of action",
+        Command {
+            namespace: String::from("input"),
+            name: String::from("unbind"),
+            executor: unbind_command,
+        },
+    );
+
+    register_command(
+        debug_state,
+        "Animate camera to fit the work area",
+        Command {
+            namespace: String::from("camera"),
+            name: String::from("fit"),
+            executor: camera_fit_command,
+        },
+    );
+
+    register_command(
+        debug_state,
+        "Animate camera to center on the world point",
+        Command {
+            namespace: String::from("camera"),
+            name: String::from("goto"),
+            executor: camera_goto_command,
+        },
+    );
+
+    register_command(
+        debug_state,
+        "Save camera pose as bookmark",
+        Command {
+            namespace: String::from("camera"),
+            name: String::from("save"),
+            executor: camera_save_command,
+        },
+    );
+
+    register_command(
+        debug_state,
+        "Animate camera to the bookmark",
+        Command {
+            namespace: String::from("camera"),
+            name: String::from("load"),
+            executor: camera_load_command,
+        },
+    );
+
+    register_command(
+        debug_state,
+        "Set camera animation easing and duration in milliseconds",
+        Command {
+            namespace: String::from("camera"),
+            name: String::from("easing"),
+            executor: camera_easing_command,
+        },
+    );
+
+    register_command(
+        debug_state,
+        "Switch grid between lines and dots",
+        Command {
+            namespace: String::from("grid"),
+            name: String::from("style"),
+            executor: grid_style_command,
+        },
+    );
+
+    register_command(
+        debug_state,
+        "Enable or disable snapping to grid",
+        Command {
+            namespace: String::from("snap"),
+            name: String::from("set_enabled"),
+            executor: snap_set_enabled_command,
+        },
+    );
+
+    register_command(
+        debug_state,
+        "Set snapping to grid lines and cell centers",
+        Command {
+            namespace: String::from("snap"),
+            name: String::from("set_targets"),
+            executor: snap_set_targets_command,
+        },
+    );
+
+    register_command(
+        debug_state,
+        "Update snap threshold in screen pixels",
+        Command {
+            namespace: String::from("snap"),
+            name: String::from("set_threshold"),
+            executor: snap_set_threshold_command,
+        },
+    );
+
+    register_command(
+        debug_state,
+        "Show or hide rulers",
+        Command {
+            namespace: String::from("rulers"),
+            name: String::from("set_enabled"),
+            executor: rulers_set_enabled_command,
+        },
+    );
+
+    register_command(
+        debug_state,
+        "Enable or disable render commands optimizer",
+        Command {
+            namespace: String::from("render"),
+            name: String::from("set_optimizer_enabled"),
+            executor: render_set_optimizer_enabled_command,
+        },
+    );
 }
 
 fn set_snapshot_interval_command(
-    _: &mut MutexGuard<DebugState>,
+    world: &mut World,
     arguments: &[CommandArgument],
 ) -> Result<(), String> {
     require(arguments.len() == 1, "bad arguments length")?;
@@ -27,8 +171,251 @@ fn set_snapshot_interval_command(
         _ => Err(String::from("Argument should be int")),
     }?;
 
-    let mut profile_state = profile::get_profile_state();
+    let mut profile_state = world
+        .resources
+        .get_mut::<ProfileState>()
+        .expect("failed to get profile state");
+
     profile::update_snapshot_interval(&mut profile_state, interval as usize);
 
     Ok(())
 }
+
+fn bind_key_command(world: &mut World, arguments: &[CommandArgument]) -> Result<(), String> {
+    require(arguments.len() == 3, "bad arguments length")?;
+
+    let (key_code, modifiers, action) = match arguments {
+        [CommandArgument::Number(key_code), CommandArgument::Number(modifiers), CommandArgument::String(action)] => {
+            Ok((*key_code as i32, *modifiers as i32, action))
+        }
+        _ => Err(String::from("Arguments should be int, int and string")),
+    }?;
+
+    let mut input_bindings = world
+        .resources
+        .get_mut::<InputBindings>()
+        .expect("failed to get input bindings");
+
+    input_bindings.bind(
+        InputTrigger::KeyPressed {
+            key_code,
+            modifiers,
+        },
+        action,
+    );
+
+    Ok(())
+}
+
+fn unbind_command(world: &mut World, arguments: &[CommandArgument]) -> Result<(), String> {
+    require(arguments.len() == 1, "bad arguments length")?;
+
+    let action = match &arguments[0] {
+        CommandArgument::String(action) => Ok(action),
+        _ => Err(String::from("Argument should be string")),
+    }?;
+
+    let mut input_bindings = world
+        .resources
+        .get_mut::<InputBindings>()
+        .expect("failed to get input bindings");
+
+    input_bindings.unbind(action);
+
+    Ok(())
+}
+
+fn camera_fit_command(world: &mut World, arguments: &[CommandArgument]) -> Result<(), String> {
+    require(arguments.is_empty(), "bad arguments length")?;
+    require(
+        animate_camera(world, gapi::CAMERA_ORTHO, CameraTarget::FitWorkArea),
+        "camera can't be animated",
+    )
+}
+
+fn camera_goto_command(world: &mut World, arguments: &[CommandArgument]) -> Result<(), String> {
+    require(arguments.len() == 2, "bad arguments length")?;
+
+    let point = match arguments {
+        [CommandArgument::Number(x), CommandArgument::Number(y)] => {
+            Ok(Vec2f::new(*x as f32, *y as f32))
+        }
+        _ => Err(String::from("Arguments should be number and number")),
+    }?;
+
+    require(
+        animate_camera(world, gapi::CAMERA_ORTHO, CameraTarget::CenterOn(point)),
+        "camera can't be animated",
+    )
+}
+
+fn camera_save_command(world: &mut World, arguments: &[CommandArgument]) -> Result<(), String> {
+    require(arguments.len() == 1, "bad arguments length")?;
+
+    let name = match &arguments[0] {
+        CommandArgument::String(name) => Ok(name),
+        _ => Err(String::from("Argument should be string")),
+    }?;
+
+    require(
+        save_camera_bookmark(world, gapi::CAMERA_ORTHO, name),
+        "camera not found",
+    )
+}
+
+fn camera_load_command(world: &mut World, arguments: &[CommandArgument]) -> Result<(), String> {
+    require(arguments.len() == 1, "bad arguments length")?;
+
+    let name = match &arguments[0] {
+        CommandArgument::String(name) => Ok(name),
+        _ => Err(String::from("Argument should be string")),
+    }?;
+
+    require(
+        load_camera_bookmark(world, gapi::CAMERA_ORTHO, name),
+        &format!("Bookmark '{}' not found", name),
+    )
+}
+
+fn camera_easing_command(world: &mut World, arguments: &[CommandArgument]) -> Result<(), String> {
+    require(arguments.len() == 2, "bad arguments length")?;
+
+    let (easing, duration) = match arguments {
+        [CommandArgument::String(easing), CommandArgument::Number(duration)] => {
+            Ok((easing, *duration))
+        }
+        _ => Err(String::from("Arguments should be string and number")),
+    }?;
+
+    let easing = Easing::from_name(easing)
+        .ok_or_else(|| String::from("Easing should be linear, ease_in, ease_out or ease_in_out"))?;
+
+    require(duration >= 0.0, "Duration can't be negative")?;
+
+    let query = <(Write<CameraAnimation2D>,)>::query();
+
+    for (mut animation,) in query.iter(world) {
+        animation.easing = easing;
+        animation.duration = Duration::from_millis(duration as u64);
+    }
+
+    Ok(())
+}
+
+fn grid_style_command(world: &mut World, arguments: &[CommandArgument]) -> Result<(), String> {
+    require(arguments.len() == 1, "bad arguments length")?;
+
+    let style = match &arguments[0] {
+        CommandArgument::String(style) if style == "lines" => Ok(GridStyle::Lines),
+        CommandArgument::String(style) if style == "dots" => Ok(GridStyle::Dots),
+        _ => Err(String::from("Argument should be \"lines\" or \"dots\"")),
+    }?;
+
+    let query = <(Write<GridComponent>,)>::query();
+
+    for (mut grid,) in query.iter(world) {
+        grid.style = style;
+    }
+
+    Ok(())
+}
+
+fn snap_set_enabled_command(
+    world: &mut World,
+    arguments: &[CommandArgument],
+) -> Result<(), String> {
+    require(arguments.len() == 1, "bad arguments length")?;
+
+    let is_enabled = match arguments[0] {
+        CommandArgument::Bool(val) => Ok(val),
+        _ => Err(String::from("Argument should be bool")),
+    }?;
+
+    get_snap_state(world).is_enabled = is_enabled;
+
+    Ok(())
+}
+
+fn snap_set_targets_command(
+    world: &mut World,
+    arguments: &[CommandArgument],
+) -> Result<(), String> {
+    require(arguments.len() == 2, "bad arguments length")?;
+
+    let (to_lines, to_centers) = match arguments {
+        [CommandArgument::Bool(to_lines), CommandArgument::Bool(to_centers)] => {
+            Ok((*to_lines, *to_centers))
+        }
+        _ => Err(String::from("Arguments should be bool and bool")),
+    }?;
+
+    let mut snap_state = get_snap_state(world);
+    snap_state.to_lines = to_lines;
+    snap_state.to_centers = to_centers;
+
+    Ok(())
+}
+
+fn snap_set_threshold_command(
+    world: &mut World,
+    arguments: &[CommandArgument],
+) -> Result<(), String> {
+    require(arguments.len() == 1, "bad arguments length")?;
+
+    let threshold = match arguments[0] {
+        CommandArgument::Number(val) => Ok(val),
+        _ => Err(String::from("Argument should be number")),
+    }?;
+
+    require(threshold >= 0.0, "Threshold can't be negative")?;
+    get_snap_state(world).threshold = threshold as f32;
+
+    Ok(())
+}
+
+fn get_snap_state(world: &World) -> FetchMut<'_, SnapState> {
+    world
+        .resources
+        .get_mut::<SnapState>()
+        .expect("failed to get snap state")
+}
+
+fn rulers_set_enabled_command(
+    world: &mut World,
+    arguments: &[CommandArgument],
+) -> Result<(), String> {
+    require(arguments.len() == 1, "bad arguments length")?;
+
+    let is_enabled = match arguments[0] {
+        CommandArgument::Bool(val) => Ok(val),
+        _ => Err(String::from("Argument should be bool")),
+    }?;
+
+    world
+        .resources
+        .get_mut::<RulersState>()
+        .expect("failed to get rulers state")
+        .is_enabled = is_enabled;
+
+    Ok(())
+}
+
+fn render_set_optimizer_enabled_command(
+    world: &mut World,
+    arguments: &[CommandArgument],
+) -> Result<(), String> {
+    require(arguments.len() == 1, "bad arguments length")?;
+
+    let is_enabled = match arguments[0] {
+        CommandArgument::Bool(val) => Ok(val),
+        _ => Err(String::from("Argument should be bool")),
+    }?;
+
+    world
+        .resources
+        .get_mut::<RenderOptimizerState>()
+        .expect("failed to get render optimizer state")
+        .is_enabled = is_enabled;
+
+    Ok(())
+}
@@ -1,22 +1,27 @@
 use std::time::{Duration, Instant};
 
+#[cfg(debug_assertions)]
+use std::cell::Cell;
 use std::collections::HashMap;
-use std::sync::{Mutex, MutexGuard};
+use std::sync::{Arc, Mutex};
 use std::thread;
 
-use lazy_static::lazy_static;
-
-use crate::debug_services::state::{DebugState, DEBUG_STATE};
-
 pub const PERFORMANCE_RECORDS_CAPACITY: usize = 512;
 pub const PERFORMANCE_COUNTER_LOG_SIZE: usize = 120; // max entires
 pub const PERFORMANCE_COUNTER_STATE_SIZE: usize = 60; // max entires
 
-lazy_static! {
-    pub static ref PROFILE_STATE: Mutex<ProfileState> = Mutex::new(ProfileState::default());
+/// Savings of the render commands optimizer in the last frame.
+#[derive(Clone, Copy, Default, Debug, PartialEq)]
+pub struct RenderStreamStats {
+    pub commands_before: usize,
+    pub commands_after: usize,
+    pub redundant_states: usize,
+    pub empty_draws: usize,
+    pub merged_draws: usize,
 }
 
 pub struct ProfileState {
+    pub render_stream: RenderStreamStats,
     pub snapshot_interval: usize,
     pub frame_timer: Instant,
     pub frame_elapsed: Duration,
@@ -27,6 +32,9 @@ pub struct ProfileState {
     pub timed_blocks: HashMap<u64, TimedBlock>,
     // TODO: Make proper id managment
     pub last_timed_block_id: u64,
+    /// Sink of the timed blocks outside of the systems.
+    pub sink: TimedBlockSink,
+    system_sinks: Vec<TimedBlockSink>,
 }
 
 impl Default for ProfileState {
@@ -34,6 +42,7 @@ impl Default for ProfileState {
         let snapshot_interval = 3;
 
         ProfileState {
+            render_stream: RenderStreamStats::default(),
             frame_counter: 0,
             snapshot_counter: 0,
             snapshot_interval,
@@ -49,6 +58,8 @@ impl Default for ProfileState {
             frame_elapsed: Duration::from_nanos(0),
             timed_blocks: HashMap::new(),
             last_timed_block_id: 0,
+            sink: TimedBlockSink::default(),
+            system_sinks: Vec::new(),
         }
     }
 }
@@ -117,7 +128,6 @@ impl Default for ClocsDebugRecord {
 
 #[derive(Clone)]
 pub struct TimedBlock {
-    manual_drop: bool,
     pub thread_id: thread::ThreadId,
     pub name: &'static str,
     pub file_name: &'static str,
@@ -131,47 +141,103 @@ impl TimedBlock {
             name,
             file_name,
             line,
-            manual_drop: false,
             thread_id: thread::current().id(),
             timer: Instant::now(),
         }
     }
 }
 
-impl Drop for TimedBlock {
-    fn drop(&mut self) {
-        if !self.manual_drop {
-            drop_timed_block(self, &mut get_profile_state());
-        }
-    }
+#[cfg(debug_assertions)]
+thread_local! {
+    static CURRENT_TIMED_BLOCK: Cell<Option<&'static str>> = const { Cell::new(None) };
 }
 
-#[macro_export]
-macro_rules! timed_block {
-    ($name:expr) => {
-        crate::debug_services::profile::TimedBlock::new($name, file!(), line!())
-    };
+/// Name of the innermost timed block alive on this thread, the render commands
+/// are attributed to it in debug builds.
+#[cfg(debug_assertions)]
+pub fn current_timed_block() -> Option<&'static str> {
+    CURRENT_TIMED_BLOCK.with(|block| block.get())
+}
+
+/// Records of the timed blocks that aren't merged into `ProfileState` yet.
+/// Every system owns a sink, so the systems don't need to write `ProfileState`
+/// and the schedule can run them in parallel.
+#[derive(Clone, Default)]
+pub struct TimedBlockSink {
+    records: Arc<Mutex<Vec<ClocsDebugRecord>>>,
 }
 
-fn get_debug_state<'a>() -> MutexGuard<'a, DebugState> {
-    DEBUG_STATE.lock().expect("failed to get debug state")
+impl ProfileState {
+    /// Creates a sink for a system, its records are merged by `merge_sinks`.
+    pub fn system_sink(&mut self) -> TimedBlockSink {
+        let sink = TimedBlockSink::default();
+        self.system_sinks.push(sink.clone());
+        sink
+    }
 }
 
-pub fn get_profile_state<'a>() -> MutexGuard<'a, ProfileState> {
-    PROFILE_STATE.lock().expect("failed to get profile state")
+/// Records the wrapped block into the sink when dropped,
+/// has to be bound to cover the block: `let _block = timed_block!(...);`.
+pub struct TimedBlockGuard {
+    sink: TimedBlockSink,
+    block: TimedBlock,
+    /// The enclosing block, it's current again when the guard is dropped.
+    #[cfg(debug_assertions)]
+    outer: Option<&'static str>,
 }
 
-pub fn push_timed_block(name: &'static str, file_name: &'static str, line: u32) -> u64 {
-    let profile = &mut get_profile_state();
+impl TimedBlockGuard {
+    pub fn new(
+        sink: TimedBlockSink,
+        name: &'static str,
+        file_name: &'static str,
+        line: u32,
+    ) -> TimedBlockGuard {
+        TimedBlockGuard {
+            sink,
+            block: TimedBlock::new(name, file_name, line),
+            #[cfg(debug_assertions)]
+            outer: CURRENT_TIMED_BLOCK.with(|block| block.replace(Some(name))),
+        }
+    }
+}
 
-    let block = TimedBlock {
-        name,
-        file_name,
-        line,
-        manual_drop: true,
-        thread_id: thread::current().id(),
-        timer: Instant::now(),
+impl Drop for TimedBlockGuard {
+    fn drop(&mut self) {
+        #[cfg(debug_assertions)]
+        CURRENT_TIMED_BLOCK.with(|block| block.set(self.outer));
+
+        let record = ClocsDebugRecord {
+            name: self.block.name,
+            file_name: self.block.file_name,
+            line: self.block.line,
+            elapsed: self.block.timer.elapsed(),
+            hits: 1,
+            thread_id: self.block.thread_id,
+        };
+
+        self.sink
+            .records
+            .lock()
+            .expect("failed to lock timed block sink")
+            .push(record);
+    }
+}
+
+#[macro_export]
+macro_rules! timed_block {
+    ($sink:expr, $name:expr) => {
+        $crate::debug_services::profile::TimedBlockGuard::new($sink, $name, file!(), line!())
     };
+}
+
+pub fn push_timed_block(
+    profile: &mut ProfileState,
+    name: &'static str,
+    file_name: &'static str,
+    line: u32,
+) -> u64 {
+    let block = TimedBlock::new(name, file_name, line);
 
     let id = profile.last_timed_block_id;
 
@@ -181,8 +247,7 @@ pub fn push_timed_block(name: &'static str, file_name: &'static str, line: u32)
     id
 }
 
-pub fn drop_timed_block_by_id(id: u64) {
-    let profile = &mut get_profile_state();
+pub fn drop_timed_block_by_id(profile: &mut ProfileState, id: u64) {
     let block = match profile.timed_blocks.get(&id) {
         Some(value) => value.clone(),
         None => {
@@ -195,55 +260,68 @@ pub fn drop_timed_block_by_id(id: u64) {
     profile.timed_blocks.remove(&id);
 }
 
-pub fn drop_timed_block(timed_block: &TimedBlock, profile_state: &mut MutexGuard<ProfileState>) {
-    let mut hits = 1;
-    let mut elapsed = timed_block.timer.elapsed();
-    let mut to_modify = false;
-    let mut modify_idx: usize = 0;
+pub fn drop_timed_block(timed_block: &TimedBlock, profile_state: &mut ProfileState) {
+    add_record(
+        profile_state,
+        ClocsDebugRecord {
+            name: timed_block.name,
+            file_name: timed_block.file_name,
+            line: timed_block.line,
+            thread_id: thread::current().id(),
+            elapsed: timed_block.timer.elapsed(),
+            hits: 1,
+        },
+    );
+}
+
+/// Moves the records of the sinks into the current frame, `frame_end` merges them
+/// after the schedules of the frame are executed.
+pub fn merge_sinks(profile_state: &mut ProfileState) {
+    let sinks: Vec<TimedBlockSink> = Some(profile_state.sink.clone())
+        .into_iter()
+        .chain(profile_state.system_sinks.iter().cloned())
+        .collect();
+
+    for sink in sinks {
+        let records: Vec<ClocsDebugRecord> = sink
+            .records
+            .lock()
+            .expect("failed to lock timed block sink")
+            .drain(..)
+            .collect();
+
+        for record in records {
+            add_record(profile_state, record);
+        }
+    }
+}
 
+fn add_record(profile_state: &mut ProfileState, record: ClocsDebugRecord) {
     let frame_counter = profile_state.frame_counter;
     let records = &mut profile_state.performance_counter_states[frame_counter].records;
 
     // NOTE(sysint64): Right now this method is faster than Map
-    for (i, c) in records.iter().enumerate() {
-        if c.name == timed_block.name
-            && c.file_name == timed_block.file_name
-            && c.line == timed_block.line
-        {
-            hits += c.hits;
-            elapsed += c.elapsed;
-            to_modify = true;
-            modify_idx = i;
+    let existing = records.iter_mut().find(|c| {
+        c.name == record.name && c.file_name == record.file_name && c.line == record.line
+    });
+
+    match existing {
+        Some(existing) => {
+            existing.elapsed += record.elapsed;
+            existing.hits += record.hits;
+            existing.thread_id = record.thread_id;
         }
-    }
-
-    if to_modify {
-        records[modify_idx] = ClocsDebugRecord {
-            name: timed_block.name,
-            file_name: timed_block.file_name,
-            line: timed_block.line,
-            thread_id: thread::current().id(),
-            elapsed,
-            hits,
-        };
-    }
-    else {
-        records.push(ClocsDebugRecord {
-            name: timed_block.name,
-            file_name: timed_block.file_name,
-            line: timed_block.line,
-            thread_id: thread::current().id(),
-            elapsed,
-            hits,
-        });
+        None => records.push(record),
     }
 }
 
-pub fn frame_start(profile_state: &mut MutexGuard<ProfileState>) {
+pub fn frame_start(profile_state: &mut ProfileState) {
     profile_state.frame_timer = Instant::now();
 }
 
-pub fn frame_end(profile_state: &mut MutexGuard<ProfileState>) {
+pub fn frame_end(profile_state: &mut ProfileState) {
+    merge_sinks(profile_state);
+
     profile_state.frame_counter += 1;
     profile_state.frame_elapsed = profile_state.frame_timer.elapsed();
 
@@ -259,7 +337,7 @@ pub fn frame_end(profile_state: &mut MutexGuard<ProfileState>) {
     }
 }
 
-fn take_snapshot(profile_state: &mut MutexGuard<ProfileState>) {
+fn take_snapshot(profile_state: &mut ProfileState) {
     profile_state.snapshot_counter += 1;
 
     if profile_state.snapshot_counter >= PERFORMANCE_COUNTER_LOG_SIZE {
@@ -306,8 +384,70 @@ fn take_snapshot(profile_state: &mut MutexGuard<ProfileState>) {
     snapshot.append(&mut records);
 }
 
-pub fn update_snapshot_interval(profile_state: &mut MutexGuard<ProfileState>, new_interval: usize) {
+pub fn update_snapshot_interval(profile_state: &mut ProfileState, new_interval: usize) {
     if new_interval <= PERFORMANCE_COUNTER_STATE_SIZE {
         profile_state.snapshot_interval = new_interval;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+    use std::time::Duration;
+
+    use crate::debug_services::profile::{merge_sinks, ProfileState};
+
+    #[test]
+    fn merge_system_sinks() {
+        let mut profile_state = ProfileState::default();
+        let sink = profile_state.system_sink();
+
+        for _ in 0..2 {
+            let _block = timed_block!(sink.clone(), "system");
+            thread::sleep(Duration::from_millis(1));
+        }
+
+        merge_sinks(&mut profile_state);
+
+        let records = &profile_state.performance_counter_states[0].records;
+
+        assert_eq!(1, records.len());
+        assert_eq!("system", records[0].name);
+        assert_eq!(2, records[0].hits);
+        assert!(records[0].elapsed >= Duration::from_millis(2));
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    fn render_command_sources() {
+        use crate::commands::{push_render_command, CommandsState, RenderCommandType};
+
+        let sink = ProfileState::default().system_sink();
+        let mut commands_state = CommandsState::default();
+
+        {
+            let _block = timed_block!(sink.clone(), "grid_system");
+            push_render_command(&mut commands_state, RenderCommandType::DrawLines);
+
+            {
+                let _block = timed_block!(sink.clone(), "render_rulers");
+                push_render_command(&mut commands_state, RenderCommandType::DrawLines);
+            }
+
+            push_render_command(&mut commands_state, RenderCommandType::DrawLines);
+        }
+
+        // A system without its own block isn't attributed to the last dropped one.
+        push_render_command(&mut commands_state, RenderCommandType::DrawLines);
+
+        assert_eq!(
+            vec![
+                Some("grid_system"),
+                Some("render_rulers"),
+                Some("grid_system"),
+                None
+            ],
+            commands_state.render_command_sources
+        );
+    }
+}
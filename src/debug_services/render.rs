@@ -1,11 +1,10 @@
-use std::sync::MutexGuard;
-
 use crate::commands::{Color, CommandsState, Rect, Vec2f};
 use crate::components::ViewPortSize;
 use crate::debug_services::profile;
 use crate::debug_services::profile::ProfileState;
 use crate::debug_services::state::*;
 use crate::gapi;
+use crate::memory::MemoryState;
 use crate::render_state::RenderState;
 
 struct Context<'a> {
@@ -13,13 +12,15 @@ struct Context<'a> {
     view_port: &'a ViewPortSize,
     commands_state: &'a mut CommandsState,
     render_state: &'a mut RenderState,
+    memory_state: &'a MemoryState,
     profile_state: &'a ProfileState,
 }
 
 pub fn render(
-    debug_state: &mut MutexGuard<DebugState>,
-    render_state: &mut MutexGuard<RenderState>,
-    profile_state: &mut MutexGuard<ProfileState>,
+    debug_state: &mut DebugState,
+    render_state: &mut RenderState,
+    profile_state: &ProfileState,
+    memory_state: &MemoryState,
     commands_state: &mut CommandsState,
     view_port: &ViewPortSize,
 ) {
@@ -30,10 +31,11 @@ pub fn render(
         view_port,
         commands_state,
         render_state,
+        memory_state,
         profile_state,
     };
 
-    let size = render_frame_time(&mut context, &profile_state);
+    let size = render_frame_time(&mut context, profile_state);
 
     context.pos.y += size.y;
     context.pos.x = 5.;
@@ -54,6 +56,7 @@ fn render_group_variables(context: &mut Context, variable: &mut GroupVariable) {
     let text_size = gapi::push_string_vec2f(
         context.commands_state,
         context.render_state,
+        context.memory_state,
         variable.name,
         context.pos,
     );
@@ -62,7 +65,7 @@ fn render_group_variables(context: &mut Context, variable: &mut GroupVariable) {
 
     gapi::push_text_shader(context.commands_state);
 
-    if variable.is_hot {
+    if variable.hover.is_hovered {
         gapi::push_color(context.commands_state, Color::rgb(0.0, 0.5, 0.0));
     }
     else {
@@ -110,6 +113,7 @@ fn render_bool_variable(context: &mut Context, variable: &mut BoolVariable) {
     gapi::push_string_vec2f(
         context.commands_state,
         context.render_state,
+        context.memory_state,
         &text,
         context.pos + Vec2f::new(-1., 1.),
     );
@@ -120,7 +124,7 @@ fn render_bool_variable(context: &mut Context, variable: &mut BoolVariable) {
     gapi::draw_text(context.commands_state);
 
     // Draw text
-    if variable.is_hot {
+    if variable.hover.is_hovered {
         gapi::push_color(context.commands_state, Color::rgb(0.0, 0.5, 0.0));
     }
     else {
@@ -130,6 +134,7 @@ fn render_bool_variable(context: &mut Context, variable: &mut BoolVariable) {
     let text_size = gapi::push_string_vec2f(
         context.commands_state,
         context.render_state,
+        context.memory_state,
         &text,
         context.pos,
     );
@@ -147,13 +152,17 @@ fn render_bool_variable(context: &mut Context, variable: &mut BoolVariable) {
 }
 
 fn render_frame_time(context: &mut Context, profile_state: &profile::ProfileState) -> Vec2f {
+    let render_stream = &profile_state.render_stream;
     let text = format!(
-        "{:.2} ms",
-        profile_state.frame_elapsed.as_nanos() as f64 / 1_000_000.0
+        "{:.2} ms, {} render commands, {} saved",
+        profile_state.frame_elapsed.as_nanos() as f64 / 1_000_000.0,
+        render_stream.commands_after,
+        render_stream.commands_before - render_stream.commands_after,
     );
     gapi::push_string_xy(
         context.commands_state,
         context.render_state,
+        context.memory_state,
         &text,
         5.0,
         5.0,
@@ -213,6 +222,7 @@ fn render_profile(context: &mut Context, profile_state: &profile::ProfileState)
         gapi::push_string_xy(
             context.commands_state,
             context.render_state,
+            context.memory_state,
             &text,
             pos.x,
             pos.y,
@@ -223,6 +233,7 @@ fn render_profile(context: &mut Context, profile_state: &profile::ProfileState)
         gapi::push_string_xy(
             context.commands_state,
             context.render_state,
+            context.memory_state,
             &text,
             pos.x,
             pos.y,
@@ -232,7 +243,8 @@ fn render_profile(context: &mut Context, profile_state: &profile::ProfileState)
         gapi::push_string_xy(
             context.commands_state,
             context.render_state,
-            &cycle.name,
+            context.memory_state,
+            cycle.name,
             pos.x,
             pos.y,
         );
@@ -246,6 +258,7 @@ fn render_profile(context: &mut Context, profile_state: &profile::ProfileState)
         gapi::push_string_xy(
             context.commands_state,
             context.render_state,
+            context.memory_state,
             &text,
             pos.x,
             pos.y,
@@ -256,6 +269,7 @@ fn render_profile(context: &mut Context, profile_state: &profile::ProfileState)
         gapi::push_string_xy(
             context.commands_state,
             context.render_state,
+            context.memory_state,
             &text,
             pos.x,
             pos.y,
@@ -269,6 +283,7 @@ fn render_profile(context: &mut Context, profile_state: &profile::ProfileState)
         gapi::push_string_xy(
             context.commands_state,
             context.render_state,
+            context.memory_state,
             &text,
             pos.x,
             pos.y,
@@ -1,14 +1,6 @@
-use std::sync::Mutex;
-
-use lazy_static::lazy_static;
-
 use crate::commands::Rect;
+use crate::components::HoverState;
 use crate::debug_services::commands::CommandsState;
-use crate::debug_services::profile::ProfileState;
-
-lazy_static! {
-    pub static ref DEBUG_STATE: Mutex<DebugState> = Mutex::new(DebugState::default());
-}
 
 pub enum DebugVariable {
     Bool(BoolVariable),
@@ -19,13 +11,13 @@ pub enum DebugVariable {
 
 #[derive(Default)]
 pub struct ProfilerLogSliderVariable {
-    pub is_hot: bool,
+    pub hover: HoverState,
     pub bounds: Rect,
 }
 
 #[derive(Default)]
 pub struct ProfilerVariable {
-    pub is_hot: bool,
+    pub hover: HoverState,
     pub bounds: Rect,
 }
 
@@ -33,7 +25,7 @@ pub struct ProfilerVariable {
 pub struct BoolVariable {
     pub name: &'static str,
     pub value: bool,
-    pub is_hot: bool,
+    pub hover: HoverState,
     pub bounds: Rect,
 }
 
@@ -41,7 +33,7 @@ pub struct GroupVariable {
     pub name: &'static str,
     pub is_expanded: bool,
     pub variables: Vec<DebugVariable>,
-    pub is_hot: bool,
+    pub hover: HoverState,
     pub bounds: Rect,
 }
 
@@ -51,7 +43,7 @@ impl GroupVariable {
             name,
             is_expanded: false,
             variables,
-            is_hot: false,
+            hover: HoverState::default(),
             bounds: Rect::ZERO,
         }
     }
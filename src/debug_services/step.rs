@@ -1,37 +1,39 @@
-use std::sync::MutexGuard;
-
-use crate::commands::{Rect, Vec2f};
-use crate::components::{LayersState, Touch, TouchState};
+use crate::commands::{Cursor, Rect};
+use crate::components::{
+    CursorState, HoverState, InputLayer, LayersState, PointerEvent, Touch, TouchState,
+};
 use crate::debug_services::state::*;
 
-// TODO(sysint64): Move to another file
-pub fn point_in_rect(point: Vec2f, rect: Rect) -> bool {
-    return point.x >= rect.pos.x
-        && point.x <= rect.pos.x + rect.size.x
-        && point.y >= rect.pos.y
-        && point.y <= rect.pos.y + rect.size.y;
-}
-
 pub fn step(
-    debug_state: &mut MutexGuard<DebugState>,
+    debug_state: &mut DebugState,
     touch_state: &TouchState,
     layers_state: &mut LayersState,
+    cursor_state: &mut CursorState,
 ) {
-    step_group_variables(touch_state, layers_state, &mut debug_state.variables);
+    step_group_variables(
+        touch_state,
+        layers_state,
+        cursor_state,
+        &mut debug_state.variables,
+    );
 }
 
 fn step_group_variables(
     touch_state: &TouchState,
     layers_state: &mut LayersState,
+    cursor_state: &mut CursorState,
     variable: &mut GroupVariable,
 ) {
-    variable.is_hot = point_in_rect(touch_state.pos, variable.bounds);
-
-    if variable.is_hot {
-        layers_state.ui_layer_is_hot = true;
-    }
+    update_hover(
+        touch_state,
+        layers_state,
+        cursor_state,
+        &mut variable.hover,
+        variable.bounds,
+        Cursor::Hand,
+    );
 
-    if variable.is_hot && touch_state.touch == Touch::Start {
+    if variable.hover.is_hovered && touch_state.touch == Touch::Start {
         variable.is_expanded = !variable.is_expanded;
     }
 
@@ -42,16 +44,21 @@ fn step_group_variables(
     for mut v in variable.variables.iter_mut() {
         match &mut v {
             DebugVariable::Bool(variable) => {
-                step_bool_variables(touch_state, layers_state, variable);
+                step_bool_variables(touch_state, layers_state, cursor_state, variable);
             }
             DebugVariable::Group(group) => {
-                step_group_variables(touch_state, layers_state, group);
+                step_group_variables(touch_state, layers_state, cursor_state, group);
             }
             DebugVariable::Profiler(profiler) => {
-                step_profiler_variable(touch_state, layers_state, profiler);
+                step_profiler_variable(touch_state, layers_state, cursor_state, profiler);
             }
             DebugVariable::ProfilerLogSlider(log_slider) => {
-                step_profiler_log_slider_variable(touch_state, layers_state, log_slider);
+                step_profiler_log_slider_variable(
+                    touch_state,
+                    layers_state,
+                    cursor_state,
+                    log_slider,
+                );
             }
         };
     }
@@ -60,15 +67,19 @@ fn step_group_variables(
 fn step_bool_variables(
     touch_state: &TouchState,
     layers_state: &mut LayersState,
+    cursor_state: &mut CursorState,
     variable: &mut BoolVariable,
 ) {
-    variable.is_hot = point_in_rect(touch_state.pos, variable.bounds);
-
-    if variable.is_hot {
-        layers_state.ui_layer_is_hot = true;
-    }
+    update_hover(
+        touch_state,
+        layers_state,
+        cursor_state,
+        &mut variable.hover,
+        variable.bounds,
+        Cursor::Hand,
+    );
 
-    if variable.is_hot && touch_state.touch == Touch::Start {
+    if variable.hover.is_hovered && touch_state.touch == Touch::Start {
         variable.value = !variable.value;
     }
 }
@@ -76,23 +87,53 @@ fn step_bool_variables(
 fn step_profiler_variable(
     touch_state: &TouchState,
     layers_state: &mut LayersState,
+    cursor_state: &mut CursorState,
     variable: &mut ProfilerVariable,
 ) {
-    variable.is_hot = point_in_rect(touch_state.pos, variable.bounds);
-
-    if variable.is_hot {
-        layers_state.ui_layer_is_hot = true;
-    }
+    update_hover(
+        touch_state,
+        layers_state,
+        cursor_state,
+        &mut variable.hover,
+        variable.bounds,
+        Cursor::Arrow,
+    );
 }
 
 fn step_profiler_log_slider_variable(
     touch_state: &TouchState,
     layers_state: &mut LayersState,
+    cursor_state: &mut CursorState,
     variable: &mut ProfilerLogSliderVariable,
 ) {
-    variable.is_hot = point_in_rect(touch_state.pos, variable.bounds);
+    update_hover(
+        touch_state,
+        layers_state,
+        cursor_state,
+        &mut variable.hover,
+        variable.bounds,
+        Cursor::Arrow,
+    );
+}
+
+fn update_hover(
+    touch_state: &TouchState,
+    layers_state: &mut LayersState,
+    cursor_state: &mut CursorState,
+    hover: &mut HoverState,
+    bounds: Rect,
+    cursor: Cursor,
+) {
+    hover.update(
+        layers_state.receives_pointer(InputLayer::DebugOverlay) && bounds.contains(touch_state.pos),
+    );
+
+    if hover.is_hovered {
+        layers_state.hit(InputLayer::DebugOverlay, PointerEvent::Consume);
+        cursor_state.cursor = cursor;
 
-    if variable.is_hot {
-        layers_state.ui_layer_is_hot = true;
+        if touch_state.touch == Touch::Start {
+            layers_state.capture(InputLayer::DebugOverlay);
+        }
     }
 }
@@ -0,0 +1,115 @@
+use std::any::Any;
+use std::cell::RefCell;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+
+use crate::RawBuffer;
+
+/// Result of every C entry point, details are available through `get_last_error`.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum StatusCode {
+    Ok = 0,
+    InvalidHandle = 1,
+    InvalidArgument = 2,
+    CommandFailed = 3,
+    Panic = 4,
+    SerializationFailed = 5,
+    IoFailed = 6,
+    ReplayMismatch = 7,
+}
+
+#[derive(Debug)]
+pub struct Error {
+    pub status: StatusCode,
+    pub message: String,
+}
+
+impl Error {
+    pub fn new(status: StatusCode, message: &str) -> Error {
+        Error {
+            status,
+            message: String::from(message),
+        }
+    }
+
+    pub fn invalid_handle() -> Error {
+        Error::new(
+            StatusCode::InvalidHandle,
+            "invalid application state handle",
+        )
+    }
+
+    pub fn invalid_argument(message: &str) -> Error {
+        Error::new(StatusCode::InvalidArgument, message)
+    }
+
+    pub fn command_failed(message: &str) -> Error {
+        Error::new(StatusCode::CommandFailed, message)
+    }
+
+    pub fn serialization_failed(message: &str) -> Error {
+        Error::new(StatusCode::SerializationFailed, message)
+    }
+
+    pub fn io_failed(message: &str) -> Error {
+        Error::new(StatusCode::IoFailed, message)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}: {}", self.status, self.message)
+    }
+}
+
+thread_local! {
+    // NOTE: Thread local, because errors may happen before there is any world
+    // to store them in, e.g. on invalid handles.
+    static LAST_ERROR: RefCell<String> = const { RefCell::new(String::new()) };
+}
+
+pub fn set_last_error(message: &str) {
+    LAST_ERROR.with(|last_error| {
+        let mut last_error = last_error.borrow_mut();
+        last_error.clear();
+        last_error.push_str(message);
+    });
+}
+
+/// Returns the message of the last failed call on the current thread,
+/// the buffer stays valid until the next failure on this thread.
+pub fn last_error() -> RawBuffer {
+    LAST_ERROR.with(|last_error| RawBuffer::from_string(last_error.borrow().as_str()))
+}
+
+/// Runs `handler` so that neither errors nor panics can unwind into the host.
+pub fn catch_errors<F>(handler: F) -> StatusCode
+where
+    F: FnOnce() -> Result<(), Error>,
+{
+    match panic::catch_unwind(AssertUnwindSafe(handler)) {
+        Ok(Ok(())) => StatusCode::Ok,
+        Ok(Err(err)) => {
+            log::error!("{}", err);
+            set_last_error(&err.message);
+            err.status
+        }
+        Err(payload) => {
+            set_last_error(&panic_message(payload.as_ref()));
+            StatusCode::Panic
+        }
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        String::from(*message)
+    }
+    else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    }
+    else {
+        String::from("unknown panic")
+    }
+}
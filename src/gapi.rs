@@ -1,10 +1,10 @@
 use crate::commands::*;
-use crate::memory;
+use crate::memory::MemoryState;
 use crate::render_state::RenderState;
 
+/// Ids of the cameras `CameraRegistry` starts with, the others are allocated by the registry.
 pub const CAMERA_UI: usize = 0;
 pub const CAMERA_ORTHO: usize = 1;
-pub const CAMERA_COUNT: usize = 2;
 
 pub fn push_color_shader(commands_state: &mut CommandsState) {
     push_render_command(commands_state, RenderCommandType::PushColorShader);
@@ -26,9 +26,9 @@ pub fn push_color(commands_state: &mut CommandsState, color: Color) {
 pub fn push_string(
     commands_state: &mut CommandsState,
     render_state: &mut RenderState,
+    memory_state: &MemoryState,
     str: &str,
 ) -> Vec2f {
-    let memory_state = memory::get_memory_state();
     let data = memory_state.frame_memory.alloc_slice_copy(str.as_bytes());
 
     push_render_command_data(
@@ -43,23 +43,25 @@ pub fn push_string(
 pub fn push_string_xy(
     commands_state: &mut CommandsState,
     render_state: &mut RenderState,
+    memory_state: &MemoryState,
     str: &str,
     x: f32,
     y: f32,
 ) -> Vec2f {
     push_vec2f_xy(commands_state, x, y);
-    push_string(commands_state, render_state, str)
+    push_string(commands_state, render_state, memory_state, str)
 }
 
 /// Returns size of string
 pub fn push_string_vec2f(
     commands_state: &mut CommandsState,
     render_state: &mut RenderState,
+    memory_state: &MemoryState,
     str: &str,
     pos: Vec2f,
 ) -> Vec2f {
     push_vec2f(commands_state, pos);
-    push_string(commands_state, render_state, str)
+    push_string(commands_state, render_state, memory_state, str)
 }
 
 pub fn push_color_rgb(commands_state: &mut CommandsState, r: f32, g: f32, b: f32) {
@@ -138,3 +140,68 @@ pub fn update_camera_position(commands_state: &mut CommandsState, id: usize, pos
     );
     push_execution_command(commands_state, ExecutionCommandType::UpdateCameraPosition);
 }
+
+pub fn update_camera_transform(
+    commands_state: &mut CommandsState,
+    id: usize,
+    transform: Transform2D,
+) {
+    push_execution_command_data(
+        commands_state,
+        ExecutionCommandType::PushInt32,
+        CommandData::int32(id as i32),
+    );
+    push_execution_command_data(
+        commands_state,
+        ExecutionCommandType::PushVec2f,
+        CommandData::vec2f(transform.x_axis),
+    );
+    push_execution_command_data(
+        commands_state,
+        ExecutionCommandType::PushVec2f,
+        CommandData::vec2f(transform.y_axis),
+    );
+    push_execution_command_data(
+        commands_state,
+        ExecutionCommandType::PushVec2f,
+        CommandData::vec2f(transform.translation),
+    );
+    push_execution_command(commands_state, ExecutionCommandType::UpdateCameraTransform);
+}
+
+pub fn update_camera_view_port(commands_state: &mut CommandsState, id: usize, view_port: Rect) {
+    push_execution_command_data(
+        commands_state,
+        ExecutionCommandType::PushInt32,
+        CommandData::int32(id as i32),
+    );
+    push_execution_command_data(
+        commands_state,
+        ExecutionCommandType::PushVec2f,
+        CommandData::vec2f(view_port.pos),
+    );
+    push_execution_command_data(
+        commands_state,
+        ExecutionCommandType::PushVec2f,
+        CommandData::vec2f(view_port.size),
+    );
+    push_execution_command(commands_state, ExecutionCommandType::UpdateCameraViewPort);
+}
+
+pub fn remove_camera(commands_state: &mut CommandsState, id: usize) {
+    push_execution_command_data(
+        commands_state,
+        ExecutionCommandType::PushInt32,
+        CommandData::int32(id as i32),
+    );
+    push_execution_command(commands_state, ExecutionCommandType::RemoveCamera);
+}
+
+pub fn set_cursor(commands_state: &mut CommandsState, cursor: Cursor) {
+    push_execution_command_data(
+        commands_state,
+        ExecutionCommandType::PushInt32,
+        CommandData::int32(cursor as i32),
+    );
+    push_execution_command(commands_state, ExecutionCommandType::SetCursor);
+}
@@ -1,74 +1,142 @@
-#[allow(dead_code, unused_imports)]
+// NOTE: The bindings aren't generated by the build, regenerate them after schema
+// changes with `scripts/regenerate_schemes.sh`.
+#[allow(warnings)]
 #[allow(clippy::all)]
 #[path = "../schemes/target/rust/commands_generated.rs"]
 mod flatbuffers_commands;
 
+#[allow(warnings)]
+#[allow(clippy::all)]
+#[path = "../schemes/target/rust/commands_capnp.rs"]
+mod commands_capnp;
+
 pub mod commands;
 pub mod components;
 pub mod debug_services;
+pub mod errors;
 pub mod gapi;
 pub mod layout;
 pub mod memory;
+pub mod picking;
+pub mod render_optimizer;
 pub mod render_state;
+pub mod render_validator;
+pub mod rulers;
+pub mod session;
+pub mod snapping;
 pub mod systems;
 
+#[cfg(test)]
+mod test_utils;
+
 mod serialize;
 
 use std::ffi::CStr;
 use std::os::raw::c_int;
+use std::path::Path;
 use std::slice;
 use std::str;
-use std::sync::{Mutex, MutexGuard};
-
-use lazy_static::lazy_static;
+use std::str::Utf8Error;
+use std::time::Duration;
 
 use commands::*;
 use components::*;
+use debug_services::profile::{ProfileState, TimedBlockSink};
+use errors::{catch_errors, Error, StatusCode};
 use legion::prelude::*;
-use render_state::RENDER_STATE;
+use legion::resource::FetchMut;
+use memory::{CommandsDataMemory, MemoryState};
+use render_optimizer::RenderOptimizerState;
+use render_state::RenderState;
+use serde::{ser, Serialize, Serializer};
 use serialize::*;
+use session::{SessionEvent, SessionRecorder};
+use systems::actions::input_actions_system;
 use systems::camera::camera_system;
+use systems::camera_animation::{
+    animate_camera, camera_animation_system, load_camera_bookmark, save_camera_bookmark,
+};
+use systems::gestures::gesture_system;
 use systems::grid::render_grid_system;
-use systems::move_camera::{move_camera_system, render_touch_system};
+use systems::hover::{cursor_system, hover_system};
+use systems::move_camera::{
+    camera_actions_system, camera_bounds_system, move_camera_system, render_touch_system,
+};
 use systems::work_area::render_work_area_system;
 
-struct ApplicationState {
+/// Engine instance, the host receives it as an opaque handle from `init_world`
+/// and passes it to every entry point until `destroy_world`.
+pub struct ApplicationState {
     _universe: Universe,
     world: World,
+    scheduler_progress: Schedule,
+    scheduler_render_pass1: Schedule,
+    scheduler_render_pass2: Schedule,
+    // NOTE: Not a world resource, because `Bump` isn't `Sync`.
+    memory_state: MemoryState,
+    session_recorder: Option<SessionRecorder>,
 }
 
 #[repr(C)]
 pub enum SerializeFormat {
     Json = 0,
+    FlatBuffers = 1,
+    CapnProto = 2,
 }
 
-static mut SCHEDULER_PROGRESS: Option<Schedule> = None;
-static mut SCHEDULER_RENDER_PASS1: Option<Schedule> = None;
-static mut SCHEDULER_RENDER_PASS2: Option<Schedule> = None;
+/// # Safety
+///
+/// `application_state` must point to memory where the new handle will be written.
+#[no_mangle]
+pub unsafe extern "C" fn init_world(application_state: *mut *mut ApplicationState) -> StatusCode {
+    catch_errors(|| {
+        if application_state.is_null() {
+            return Err(Error::invalid_argument("application state pointer is null"));
+        }
+
+        let handle = Box::into_raw(Box::new(create_application_state()));
+        application_state.write(handle);
 
-lazy_static! {
-    static ref APPLICATION_STATE: Mutex<Option<ApplicationState>> = Mutex::new(None);
+        Ok(())
+    })
 }
 
-#[no_mangle]
-pub extern "C" fn init_world() {
-    env_logger::init();
-    debug_services::init();
+fn create_application_state() -> ApplicationState {
+    let _ = env_logger::try_init();
 
     let universe = Universe::new();
     let mut world = universe.create_world();
 
+    debug_services::init(&mut world.resources);
+
     world.resources.insert(CommandsState::default());
+    world.resources.insert(RenderState::default());
     world.resources.insert(ViewPortSize::default());
     world.resources.insert(TouchState::default());
+    world.resources.insert(KeyboardState::default());
+    world.resources.insert(ScrollState::default());
+    world.resources.insert(FrameClock::default());
+    world.resources.insert(GestureState::default());
+    world.resources.insert(InputBindings::default());
+    world.resources.insert(CursorState::default());
     world.resources.insert(LayersState::default());
+    world.resources.insert(CameraRegistry::default());
+    world.resources.insert(CameraBookmarks::default());
+    world.resources.insert(SnapState::default());
+    world.resources.insert(RulersState::default());
+    world.resources.insert(RenderOptimizerState::default());
 
     world.insert(
         (),
         vec![(
             GridComponent {
-                color: Color::rgba(0.0, 0.0, 0.0, 0.1),
                 step: 16,
+                subdivisions: 4,
+                min_spacing: 8.0,
+                minor_color: Color::rgba(0.0, 0.0, 0.0, 0.06),
+                major_color: Color::rgba(0.0, 0.0, 0.0, 0.12),
+                axis_color: Color::rgba(0.0, 0.0, 0.0, 0.3),
+                style: GridStyle::Lines,
             },
             Camera2DPositionListener::new(gapi::CAMERA_ORTHO),
         )],
@@ -76,11 +144,19 @@ pub extern "C" fn init_world() {
 
     world.insert(
         (),
-        vec![(WorkAreaComponent {
-            title: String::from("Hello world!"),
-            color: Color::rgba(0.0, 0.0, 1.0, 0.3),
-            size: Vec2f::new(640.0, 480.0),
-        },)],
+        vec![(
+            WorkAreaComponent {
+                title: String::from("Hello world!"),
+                color: Color::rgba(0.0, 0.0, 1.0, 0.3),
+                size: Vec2f::new(640.0, 480.0),
+            },
+            Bounds {
+                rect: Rect::new(Vec2f::ZERO, Vec2f::new(640.0, 480.0)),
+                z_order: 0,
+            },
+            Hoverable::new(Cursor::Crosshair),
+            Camera2DPositionListener::new(gapi::CAMERA_ORTHO),
+        )],
     );
 
     world.insert(
@@ -89,192 +165,422 @@ pub extern "C" fn init_world() {
             Camera2D {
                 id: gapi::CAMERA_ORTHO,
                 pos: Vec2f::new(-320.0, -240.0),
+                zoom: 1.0,
+                rotation: 0.0,
+                y_axis: YAxis::Down,
             },
             CameraMovable2D::default(),
+            CameraBounds2D::default(),
+            CameraAnimation2D::default(),
             TouchState::default(),
             Camera2DPositionListener::new(gapi::CAMERA_ORTHO),
         )],
     );
 
-    unsafe {
-        SCHEDULER_PROGRESS = Some(
-            Schedule::builder()
-                .add_system(camera_system())
-                .add_system(move_camera_system())
-                .flush()
-                .build(),
-        );
-
-        SCHEDULER_RENDER_PASS1 = Some(Schedule::builder().flush().build());
-
-        SCHEDULER_RENDER_PASS2 = Some(
-            Schedule::builder()
-                .add_system(render_touch_system())
-                .add_system(render_grid_system())
-                .add_system(render_work_area_system())
-                .flush()
-                .build(),
-        );
-    }
+    // Every system gets its own sink, the records are merged at the frame end.
+    let (scheduler_progress, scheduler_render_pass1, scheduler_render_pass2) = {
+        let mut profile_state = get_profile_state(&world);
+
+        let scheduler_progress = Schedule::builder()
+            .add_system(gesture_system(profile_state.system_sink()))
+            .add_system(input_actions_system(profile_state.system_sink()))
+            .flush()
+            .add_system(camera_system(profile_state.system_sink()))
+            .add_system(hover_system(profile_state.system_sink()))
+            .add_system(move_camera_system(profile_state.system_sink()))
+            .add_system(camera_actions_system(profile_state.system_sink()))
+            .add_system(camera_animation_system(profile_state.system_sink()))
+            .add_system(camera_bounds_system(profile_state.system_sink()))
+            .add_system(cursor_system(profile_state.system_sink()))
+            .flush()
+            .build();
+
+        let scheduler_render_pass1 = Schedule::builder().flush().build();
+
+        let scheduler_render_pass2 = Schedule::builder()
+            .add_system(render_touch_system(profile_state.system_sink()))
+            .add_system(render_grid_system(profile_state.system_sink()))
+            .add_system(render_work_area_system(profile_state.system_sink()))
+            .flush()
+            .build();
+
+        (
+            scheduler_progress,
+            scheduler_render_pass1,
+            scheduler_render_pass2,
+        )
+    };
 
-    let application_state = ApplicationState {
+    ApplicationState {
         _universe: universe,
         world,
-    };
-
-    APPLICATION_STATE
-        .lock()
-        .expect("failed to get application state")
-        .replace(application_state);
-}
-
-fn get_application_state<'a>() -> MutexGuard<'a, Option<ApplicationState>> {
-    APPLICATION_STATE
-        .lock()
-        .expect("failed to get application state")
+        scheduler_progress,
+        scheduler_render_pass1,
+        scheduler_render_pass2,
+        memory_state: MemoryState::default(),
+        session_recorder: None,
+    }
 }
 
+/// # Safety
+///
+/// `application_state` must be a handle returned by `init_world` that hasn't
+/// been destroyed yet, it mustn't be used after this call.
 #[no_mangle]
-pub extern "C" fn frame_start() {
-    debug_services::debug_frame_start();
+pub unsafe extern "C" fn destroy_world(application_state: *mut ApplicationState) -> StatusCode {
+    catch_errors(|| {
+        if application_state.is_null() {
+            return Err(Error::invalid_handle());
+        }
+
+        let application_state = Box::from_raw(application_state);
+
+        match application_state.session_recorder {
+            Some(recorder) => recorder.finish(),
+            None => Ok(()),
+        }
+    })
 }
 
+/// Starts recording everything the host feeds into the world, the events
+/// are written to `path` as they happen and the recording is finished
+/// by `stop_session_recording` or `destroy_world`.
+///
+/// # Safety
+///
+/// `application_state` must be null or a handle returned by `init_world`
+/// that hasn't been destroyed yet, `path` must be a valid null-terminated string.
 #[no_mangle]
-pub extern "C" fn frame_end() {
-    match get_application_state().as_mut() {
-        Some(state) => {
-            let mut touch_state = state
-                .world
-                .resources
-                .get_mut::<TouchState>()
-                .expect("failed to get touch state");
+pub unsafe extern "C" fn start_session_recording(
+    application_state: *mut ApplicationState,
+    path: *const i8,
+) -> StatusCode {
+    catch_errors(|| {
+        let state = get_application_state(application_state)?;
+
+        if path.is_null() {
+            return Err(Error::invalid_argument("session path is null"));
+        }
 
-            touch_state.touch = Touch::None;
+        let path = CStr::from_ptr(path)
+            .to_str()
+            .map_err(|err| Error::invalid_argument(&err.to_string()))?;
 
-            let mut layers_state = state
-                .world
-                .resources
-                .get_mut::<LayersState>()
-                .expect("failed to get layers state");
+        state.session_recorder = Some(SessionRecorder::new(Path::new(path))?);
 
-            layers_state.reset();
+        Ok(())
+    })
+}
 
-            debug_services::debug_frame_end();
-        }
-        None => {
-            panic!("failed to get application state");
+/// # Safety
+///
+/// `application_state` must be null or a handle returned by `init_world`
+/// that hasn't been destroyed yet.
+#[no_mangle]
+pub unsafe extern "C" fn stop_session_recording(
+    application_state: *mut ApplicationState,
+) -> StatusCode {
+    catch_errors(|| {
+        let state = get_application_state(application_state)?;
+
+        match state.session_recorder.take() {
+            Some(recorder) => recorder.finish(),
+            None => Err(Error::invalid_argument("session isn't being recorded")),
         }
-    }
+    })
 }
 
+/// Replays the session recorded to `path` in a fresh world, with `assert_streams`
+/// fails with `StatusCode::ReplayMismatch` when the produced command streams differ
+/// from the recorded ones.
+///
+/// # Safety
+///
+/// `path` must be a valid null-terminated string.
 #[no_mangle]
-pub extern "C" fn flush() {
-    match get_application_state().as_mut() {
-        Some(state) => {
-            state_flush(state);
-            delete_action_entities(&mut state.world);
+pub unsafe extern "C" fn replay_session(path: *const i8, assert_streams: bool) -> StatusCode {
+    catch_errors(|| {
+        if path.is_null() {
+            return Err(Error::invalid_argument("session path is null"));
         }
-        None => {
-            panic!("failed to get application state");
-        }
-    }
+
+        let path = CStr::from_ptr(path)
+            .to_str()
+            .map_err(|err| Error::invalid_argument(&err.to_string()))?;
+
+        session::replay_file(Path::new(path), assert_streams)
+    })
 }
 
+/// Returns the message of the last failed call on the calling thread.
 #[no_mangle]
-pub extern "C" fn step() {
-    match get_application_state().as_mut() {
-        Some(state) => {
-            {
-                handle_request_commands(state);
-
-                let touch_state = state
-                    .world
-                    .resources
-                    .get::<TouchState>()
-                    .expect("failed to get touch state");
-
-                let mut layers_state = state
-                    .world
-                    .resources
-                    .get_mut::<LayersState>()
-                    .expect("failed to get layers state");
-
-                debug_services::ui_step_pass(&touch_state, &mut layers_state);
-            }
+pub extern "C" fn get_last_error() -> RawBuffer {
+    errors::last_error()
+}
 
-            unsafe {
-                SCHEDULER_PROGRESS
-                    .as_mut()
-                    .expect("failed to get scheduler")
-                    .execute(&mut state.world);
-            }
-        }
-        None => {
-            panic!("failed to get application state");
-        }
+/// # Safety
+///
+/// `application_state` must be null or a handle returned by `init_world`
+/// that hasn't been destroyed yet, the reference mustn't outlive the call
+/// from the host.
+unsafe fn get_application_state<'a>(
+    application_state: *mut ApplicationState,
+) -> Result<&'a mut ApplicationState, Error> {
+    application_state.as_mut().ok_or_else(Error::invalid_handle)
+}
+
+/// # Safety
+///
+/// `result` must be null or valid for writes.
+unsafe fn write_result<T>(result: *mut T, value: T) -> Result<(), Error> {
+    if result.is_null() {
+        return Err(Error::invalid_argument("result pointer is null"));
+    }
+
+    result.write(value);
+    Ok(())
+}
+
+fn record_session_event(application_state: &mut ApplicationState, event: SessionEvent) {
+    if let Some(recorder) = &mut application_state.session_recorder {
+        recorder.record(event);
     }
 }
 
+fn get_profile_state(world: &World) -> FetchMut<'_, ProfileState> {
+    world
+        .resources
+        .get_mut::<ProfileState>()
+        .expect("failed to get profile state")
+}
+
+fn profile_sink(world: &World) -> TimedBlockSink {
+    get_profile_state(world).sink.clone()
+}
+
+/// # Safety
+///
+/// `application_state` must be null or a handle returned by `init_world`
+/// that hasn't been destroyed yet.
 #[no_mangle]
-pub extern "C" fn render_pass1() {
-    match get_application_state().as_mut() {
-        Some(state) => {
-            unsafe {
-                SCHEDULER_RENDER_PASS1
-                    .as_mut()
-                    .expect("failed to get scheduler")
-                    .execute(&mut state.world);
-            }
+pub unsafe extern "C" fn frame_start(application_state: *mut ApplicationState) -> StatusCode {
+    catch_errors(|| {
+        let state = get_application_state(application_state)?;
+        debug_services::debug_frame_start(&mut get_profile_state(&state.world));
 
-            let commands_state = &mut state
+        let time = {
+            let mut frame_clock = state
                 .world
                 .resources
-                .get_mut::<CommandsState>()
-                .expect("failed to get commands state");
+                .get_mut::<FrameClock>()
+                .expect("failed to get frame clock");
 
-            let view_port = state
-                .world
-                .resources
-                .get::<ViewPortSize>()
-                .expect("failed to get commands state");
+            frame_clock.tick();
+            frame_clock.time
+        };
 
-            debug_services::render_pass(commands_state, &view_port);
-        }
-        None => {
-            panic!("failed to get application state");
-        }
-    }
+        record_session_event(state, SessionEvent::FrameStart { time });
+
+        Ok(())
+    })
 }
 
+/// # Safety
+///
+/// `application_state` must be null or a handle returned by `init_world`
+/// that hasn't been destroyed yet.
 #[no_mangle]
-pub extern "C" fn render_pass2() {
-    match get_application_state().as_mut() {
-        Some(state) => {
-            unsafe {
-                SCHEDULER_RENDER_PASS2
-                    .as_mut()
-                    .expect("failed to get scheduler")
-                    .execute(&mut state.world);
-            }
+pub unsafe extern "C" fn frame_end(application_state: *mut ApplicationState) -> StatusCode {
+    catch_errors(|| {
+        let state = get_application_state(application_state)?;
 
-            let commands_state = &mut state
-                .world
-                .resources
-                .get_mut::<CommandsState>()
-                .expect("failed to get commands state");
+        let mut touch_state = state
+            .world
+            .resources
+            .get_mut::<TouchState>()
+            .expect("failed to get touch state");
 
-            let view_port = state
+        touch_state.touch = Touch::None;
+
+        let mut keyboard_state = state
+            .world
+            .resources
+            .get_mut::<KeyboardState>()
+            .expect("failed to get keyboard state");
+
+        keyboard_state.reset();
+
+        let mut scroll_state = state
+            .world
+            .resources
+            .get_mut::<ScrollState>()
+            .expect("failed to get scroll state");
+
+        scroll_state.reset();
+
+        let mut cursor_state = state
+            .world
+            .resources
+            .get_mut::<CursorState>()
+            .expect("failed to get cursor state");
+
+        cursor_state.reset();
+
+        debug_services::debug_frame_end(&mut get_profile_state(&state.world));
+
+        if let Some(recorder) = &mut state.session_recorder {
+            let frame_elapsed = get_profile_state(&state.world).frame_elapsed;
+            recorder.record(SessionEvent::FrameEnd { frame_elapsed });
+        }
+
+        Ok(())
+    })
+}
+
+/// # Safety
+///
+/// `application_state` must be null or a handle returned by `init_world`
+/// that hasn't been destroyed yet.
+#[no_mangle]
+pub unsafe extern "C" fn flush(application_state: *mut ApplicationState) -> StatusCode {
+    catch_errors(|| {
+        let state = get_application_state(application_state)?;
+
+        if let Some(recorder) = &mut state.session_recorder {
+            let commands_state = state
                 .world
                 .resources
-                .get::<ViewPortSize>()
+                .get::<CommandsState>()
                 .expect("failed to get commands state");
 
-            debug_services::render_pass(commands_state, &view_port);
-        }
-        None => {
-            panic!("failed to get application state");
+            recorder.try_record(SessionEvent::flush(&commands_state));
         }
+
+        state_flush(state);
+        delete_action_entities(&mut state.world);
+
+        Ok(())
+    })
+}
+
+/// # Safety
+///
+/// `application_state` must be null or a handle returned by `init_world`
+/// that hasn't been destroyed yet.
+#[no_mangle]
+pub unsafe extern "C" fn step(application_state: *mut ApplicationState) -> StatusCode {
+    catch_errors(|| {
+        let state = get_application_state(application_state)?;
+        record_session_event(state, SessionEvent::Step);
+
+        handle_request_commands(state);
+        begin_input_layers_step(&state.world.resources);
+        debug_services::ui_step_pass(&state.world.resources);
+
+        state.scheduler_progress.execute(&mut state.world);
+
+        Ok(())
+    })
+}
+
+/// # Safety
+///
+/// `application_state` must be null or a handle returned by `init_world`
+/// that hasn't been destroyed yet.
+#[no_mangle]
+pub unsafe extern "C" fn render_pass1(application_state: *mut ApplicationState) -> StatusCode {
+    catch_errors(|| {
+        let state = get_application_state(application_state)?;
+        record_session_event(state, SessionEvent::RenderPass1);
+
+        state.scheduler_render_pass1.execute(&mut state.world);
+        debug_services::render_pass(&state.world.resources, &state.memory_state);
+
+        Ok(())
+    })
+}
+
+/// # Safety
+///
+/// `application_state` must be null or a handle returned by `init_world`
+/// that hasn't been destroyed yet.
+#[no_mangle]
+pub unsafe extern "C" fn render_pass2(application_state: *mut ApplicationState) -> StatusCode {
+    catch_errors(|| {
+        let state = get_application_state(application_state)?;
+        record_session_event(state, SessionEvent::RenderPass2);
+
+        state.scheduler_render_pass2.execute(&mut state.world);
+        rulers::render_rulers(&mut state.world, &state.memory_state);
+        debug_services::render_pass(&state.world.resources, &state.memory_state);
+
+        #[cfg(debug_assertions)]
+        validate_render_commands(&state.world);
+
+        optimize_render_commands(&state.world);
+
+        Ok(())
+    })
+}
+
+/// Logs the render commands of the frame the host would fail to execute,
+/// runs before the optimizer changes the commands.
+#[cfg(debug_assertions)]
+fn validate_render_commands(world: &World) {
+    let commands_state = world
+        .resources
+        .get::<CommandsState>()
+        .expect("failed to get commands state");
+
+    for issue in render_validator::validate(&commands_state.render_commands) {
+        let source = commands_state
+            .render_command_sources
+            .get(issue.command_index)
+            .copied()
+            .flatten()
+            .unwrap_or("<unknown>");
+
+        log::warn!(
+            "invalid render command {} emitted by {}: {}",
+            issue.command_index,
+            source,
+            issue.kind
+        );
+    }
+}
+
+/// The host reads the render commands after the second render pass.
+fn optimize_render_commands(world: &World) {
+    let is_enabled = world
+        .resources
+        .get::<RenderOptimizerState>()
+        .expect("failed to get render optimizer state")
+        .is_enabled;
+
+    if !is_enabled {
+        return;
     }
+
+    let _block = debug_services::timed_block!(profile_sink(world), "optimize_render_commands");
+
+    let mut commands_state = world
+        .resources
+        .get_mut::<CommandsState>()
+        .expect("failed to get commands state");
+
+    get_profile_state(world).render_stream =
+        render_optimizer::optimize(&mut commands_state.render_commands);
+}
+
+fn begin_input_layers_step(resources: &Resources) {
+    let touch_state = resources
+        .get::<TouchState>()
+        .expect("failed to get touch state");
+
+    resources
+        .get_mut::<LayersState>()
+        .expect("failed to get layers state")
+        .begin_step(&touch_state);
 }
 
 fn delete_action_entities(world: &mut World) {
@@ -298,16 +604,19 @@ fn handle_request_commands(application_state: &mut ApplicationState) {
         .to_vec();
 
     for command in state {
-        handle_request_command(application_state, &command);
+        handle_request_command(
+            &mut application_state.world,
+            &mut application_state.memory_state.commands_data,
+            &command,
+        );
     }
 }
 
-fn handle_request_command(application_state: &mut ApplicationState, command: &RequestCommand) {
-    let world = &mut application_state.world;
-
-    // TODO:
-    let memory = &mut memory::get_memory_state().commands_data;
-
+fn handle_request_command(
+    world: &mut World,
+    memory: &mut CommandsDataMemory,
+    command: &RequestCommand,
+) {
     match command {
         RequestCommand {
             command_type: RequestCommandType::PushVec2f,
@@ -321,6 +630,12 @@ fn handle_request_command(application_state: &mut ApplicationState, command: &Re
         } => {
             memory.vec2i_data.push(*vec2i);
         }
+        RequestCommand {
+            command_type: RequestCommandType::PushInt32,
+            data: CommandData { int32, .. },
+        } => {
+            memory.int32_data.push(*int32);
+        }
         RequestCommand {
             command_type: RequestCommandType::SetViewportSize,
             ..
@@ -338,8 +653,11 @@ fn handle_request_command(application_state: &mut ApplicationState, command: &Re
             command_type: RequestCommandType::OnTouchStart,
             ..
         } => {
+            let pointer_id = memory.int32_data.pop().unwrap_or(0);
+            let time = pop_touch_time(world, memory);
+
             if let Some(vec2f) = memory.vec2f_data.pop() {
-                on_touch_start(world, vec2f);
+                on_touch_start(world, pointer_id, vec2f, time);
             }
             else {
                 log::warn!("data have not been provided to OnTouchStart request command");
@@ -351,8 +669,11 @@ fn handle_request_command(application_state: &mut ApplicationState, command: &Re
             command_type: RequestCommandType::OnTouchEnd,
             ..
         } => {
+            let pointer_id = memory.int32_data.pop().unwrap_or(0);
+            let time = pop_touch_time(world, memory);
+
             if let Some(vec2f) = memory.vec2f_data.pop() {
-                on_touch_end(world, vec2f);
+                on_touch_end(world, pointer_id, vec2f, time);
             }
             else {
                 log::warn!("data have not been provided to OnTouchEnd request command");
@@ -364,13 +685,157 @@ fn handle_request_command(application_state: &mut ApplicationState, command: &Re
             command_type: RequestCommandType::OnTouchMove,
             ..
         } => {
+            let pointer_id = memory.int32_data.pop().unwrap_or(0);
+            let time = pop_touch_time(world, memory);
+
             if let Some(vec2f) = memory.vec2f_data.pop() {
-                on_touch_move(world, vec2f);
+                on_touch_move(world, pointer_id, vec2f, time);
             }
             else {
                 log::warn!("data have not been provided to OnTouchMove request command");
             }
 
+            memory.clear();
+        }
+        RequestCommand {
+            command_type: RequestCommandType::OnKeyDown,
+            ..
+        } => {
+            let modifiers = memory.int32_data.pop();
+            let key_code = memory.int32_data.pop();
+
+            if let (Some(key_code), Some(modifiers)) = (key_code, modifiers) {
+                on_key_down(world, key_code, modifiers);
+            }
+            else {
+                log::warn!("data have not been provided to OnKeyDown request command");
+            }
+
+            memory.clear();
+        }
+        RequestCommand {
+            command_type: RequestCommandType::OnKeyUp,
+            ..
+        } => {
+            let modifiers = memory.int32_data.pop();
+            let key_code = memory.int32_data.pop();
+
+            if let (Some(key_code), Some(modifiers)) = (key_code, modifiers) {
+                on_key_up(world, key_code, modifiers);
+            }
+            else {
+                log::warn!("data have not been provided to OnKeyUp request command");
+            }
+
+            memory.clear();
+        }
+        RequestCommand {
+            command_type: RequestCommandType::OnTextInput,
+            data: CommandData { string, .. },
+        } => {
+            match string.data_to_string() {
+                Ok(text) => on_text_input(world, &text),
+                Err(err) => log::warn!("invalid text in OnTextInput request command: {}", err),
+            }
+
+            memory.clear();
+        }
+        RequestCommand {
+            command_type: RequestCommandType::OnScroll,
+            ..
+        } => {
+            let modifiers = memory.int32_data.pop();
+            let delta = memory.vec2f_data.pop();
+
+            if let (Some(delta), Some(modifiers)) = (delta, modifiers) {
+                on_scroll(world, delta, modifiers);
+            }
+            else {
+                log::warn!("data have not been provided to OnScroll request command");
+            }
+
+            memory.clear();
+        }
+        RequestCommand {
+            command_type: RequestCommandType::CameraFit,
+            ..
+        } => {
+            let camera_id = pop_camera_id(memory);
+
+            if !animate_camera(world, camera_id, CameraTarget::FitWorkArea) {
+                log::warn!(
+                    "camera {} can't be animated by CameraFit request command",
+                    camera_id
+                );
+            }
+
+            memory.clear();
+        }
+        RequestCommand {
+            command_type: RequestCommandType::CameraGoTo,
+            ..
+        } => {
+            let camera_id = pop_camera_id(memory);
+
+            if let Some(vec2f) = memory.vec2f_data.pop() {
+                if !animate_camera(world, camera_id, CameraTarget::CenterOn(vec2f)) {
+                    log::warn!(
+                        "camera {} can't be animated by CameraGoTo request command",
+                        camera_id
+                    );
+                }
+            }
+            else {
+                log::warn!("data have not been provided to CameraGoTo request command");
+            }
+
+            memory.clear();
+        }
+        RequestCommand {
+            command_type: RequestCommandType::CameraSaveBookmark,
+            data: CommandData { string, .. },
+        } => {
+            let camera_id = pop_camera_id(memory);
+
+            match string.data_to_string() {
+                Ok(name) => {
+                    if !save_camera_bookmark(world, camera_id, &name) {
+                        log::warn!(
+                            "camera {} not found by CameraSaveBookmark request command",
+                            camera_id
+                        );
+                    }
+                }
+                Err(err) => log::warn!(
+                    "invalid name in CameraSaveBookmark request command: {}",
+                    err
+                ),
+            }
+
+            memory.clear();
+        }
+        RequestCommand {
+            command_type: RequestCommandType::CameraLoadBookmark,
+            data: CommandData { string, .. },
+        } => {
+            let camera_id = pop_camera_id(memory);
+
+            match string.data_to_string() {
+                Ok(name) => {
+                    if !load_camera_bookmark(world, camera_id, &name) {
+                        log::warn!(
+                            "bookmark '{}' can't be loaded to camera {}",
+                            name,
+                            camera_id
+                        );
+                    }
+                }
+                Err(err) => log::warn!(
+                    "invalid name in CameraLoadBookmark request command: {}",
+                    err
+                ),
+            }
+
             memory.clear();
         }
     }
@@ -383,18 +848,32 @@ fn state_flush(application_state: &mut ApplicationState) {
         .get_mut::<CommandsState>()
         .expect("failed to get commands state");
 
-    memory::flush();
+    application_state.memory_state.flush();
 
     state.render_commands.clear();
+    #[cfg(debug_assertions)]
+    state.render_command_sources.clear();
     state.exec_commands.clear();
     state.request_commands.clear();
 
-    let render_state = &mut RENDER_STATE.lock().expect("failed to get render state");
+    let mut render_state = application_state
+        .world
+        .resources
+        .get_mut::<RenderState>()
+        .expect("failed to get render state");
+
     render_state.bump_cursor();
 }
 
-pub fn render_state_flush() {
-    let render_state = &mut RENDER_STATE.lock().expect("failed to get render state");
+pub fn render_state_flush(state: &mut ApplicationState) {
+    record_session_event(state, SessionEvent::RenderStateFlush);
+
+    let mut render_state = state
+        .world
+        .resources
+        .get_mut::<RenderState>()
+        .expect("failed to get render state");
+
     render_state.clear();
 }
 
@@ -420,11 +899,10 @@ impl RawBuffer {
         }
     }
 
-    pub fn data_to_string(&self) -> String {
+    pub fn data_to_string(&self) -> Result<String, Utf8Error> {
         let data = unsafe { slice::from_raw_parts(self.data, self.length) };
-        // TODO: Handle error
-        let utf8_str = str::from_utf8(data).unwrap();
-        String::from(utf8_str)
+        let utf8_str = str::from_utf8(data)?;
+        Ok(String::from(utf8_str))
     }
 }
 
@@ -437,6 +915,16 @@ impl Default for RawBuffer {
     }
 }
 
+impl Serialize for RawBuffer {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let string = self.data_to_string().map_err(ser::Error::custom)?;
+        serializer.serialize_str(&string)
+    }
+}
+
 impl Clone for RawBuffer {
     fn clone(&self) -> Self {
         RawBuffer {
@@ -464,286 +952,656 @@ pub struct ExecutionCommands {
     pub length: c_int,
 }
 
+/// # Safety
+///
+/// `application_state` must be null or a handle returned by `init_world`
+/// that hasn't been destroyed yet, `id` must be null or valid for writes.
+/// `name` and `file_name` must be valid null-terminated strings that outlive
+/// the engine, profiler keeps references to them.
 #[no_mangle]
 pub unsafe extern "C" fn c_push_timed_block(
+    application_state: *mut ApplicationState,
     name: *const i8,
     file_name: *const i8,
     line: u32,
-) -> u64 {
-    debug_services::profile::push_timed_block(
-        CStr::from_ptr(name).to_str().unwrap(),
-        CStr::from_ptr(file_name).to_str().unwrap(),
-        line,
-    )
+    id: *mut u64,
+) -> StatusCode {
+    catch_errors(|| {
+        let state = get_application_state(application_state)?;
+
+        if name.is_null() || file_name.is_null() {
+            return Err(Error::invalid_argument("timed block name is null"));
+        }
+
+        let name = CStr::from_ptr(name)
+            .to_str()
+            .map_err(|err| Error::invalid_argument(&err.to_string()))?;
+
+        let file_name = CStr::from_ptr(file_name)
+            .to_str()
+            .map_err(|err| Error::invalid_argument(&err.to_string()))?;
+
+        let block_id = debug_services::profile::push_timed_block(
+            &mut get_profile_state(&state.world),
+            name,
+            file_name,
+            line,
+        );
+
+        write_result(id, block_id)
+    })
 }
 
+/// # Safety
+///
+/// `application_state` must be null or a handle returned by `init_world`
+/// that hasn't been destroyed yet.
 #[no_mangle]
-pub extern "C" fn c_drop_timed_block(id: u64) {
-    debug_services::profile::drop_timed_block_by_id(id);
+pub unsafe extern "C" fn c_drop_timed_block(
+    application_state: *mut ApplicationState,
+    id: u64,
+) -> StatusCode {
+    catch_errors(|| {
+        let state = get_application_state(application_state)?;
+        debug_services::profile::drop_timed_block_by_id(&mut get_profile_state(&state.world), id);
+
+        Ok(())
+    })
 }
 
+/// # Safety
+///
+/// `application_state` must be null or a handle returned by `init_world`
+/// that hasn't been destroyed yet.
+/// `render_commands` must be null or valid for writes.
 #[no_mangle]
-pub extern "C" fn c_get_render_commands() -> RenderCommands {
-    debug_services::timed_block!("c_get_render_commands");
+pub unsafe extern "C" fn c_get_render_commands(
+    application_state: *mut ApplicationState,
+    render_commands: *mut RenderCommands,
+) -> StatusCode {
+    catch_errors(|| {
+        let application_state = get_application_state(application_state)?;
+        let _block = debug_services::timed_block!(
+            profile_sink(&application_state.world),
+            "c_get_render_commands"
+        );
 
-    match get_application_state().as_mut() {
-        Some(application_state) => {
-            let state = application_state
-                .world
-                .resources
-                .get::<CommandsState>()
-                .expect("failed to get commands state");
+        let state = application_state
+            .world
+            .resources
+            .get::<CommandsState>()
+            .expect("failed to get commands state");
 
+        write_result(
+            render_commands,
             RenderCommands {
                 items: state.render_commands.as_ptr(),
                 length: state.render_commands.len() as c_int,
-            }
-        }
-        None => {
-            panic!("failed to get application state");
-        }
-    }
+            },
+        )
+    })
 }
 
+/// # Safety
+///
+/// `application_state` must be null or a handle returned by `init_world`
+/// that hasn't been destroyed yet.
+/// `exec_commands` must be null or valid for writes.
 #[no_mangle]
-pub extern "C" fn c_get_exec_commands() -> ExecutionCommands {
-    debug_services::timed_block!("c_get_exec_commands");
+pub unsafe extern "C" fn c_get_exec_commands(
+    application_state: *mut ApplicationState,
+    exec_commands: *mut ExecutionCommands,
+) -> StatusCode {
+    catch_errors(|| {
+        let application_state = get_application_state(application_state)?;
+        let _block = debug_services::timed_block!(
+            profile_sink(&application_state.world),
+            "c_get_exec_commands"
+        );
 
-    match get_application_state().as_mut() {
-        Some(application_state) => {
-            let state = application_state
-                .world
-                .resources
-                .get::<CommandsState>()
-                .expect("failed to get commands state");
+        let state = application_state
+            .world
+            .resources
+            .get::<CommandsState>()
+            .expect("failed to get commands state");
 
+        write_result(
+            exec_commands,
             ExecutionCommands {
                 items: state.exec_commands.as_ptr(),
                 length: state.exec_commands.len() as c_int,
-            }
-        }
-        None => {
-            panic!("failed to get application state");
-        }
-    }
+            },
+        )
+    })
 }
 
+/// Console command failures are reported as `StatusCode::CommandFailed`
+/// with the command error available through `get_last_error`.
+///
+/// # Safety
+///
+/// `application_state` must be null or a handle returned by `init_world`
+/// that hasn't been destroyed yet.
 #[no_mangle]
-pub extern "C" fn c_execute_command(data: RawBuffer) {
-    debug_services::timed_block!("c_execute_command");
+pub unsafe extern "C" fn c_execute_command(
+    application_state: *mut ApplicationState,
+    data: RawBuffer,
+) -> StatusCode {
+    catch_errors(|| {
+        let application_state = get_application_state(application_state)?;
+        let _block = debug_services::timed_block!(
+            profile_sink(&application_state.world),
+            "c_execute_command"
+        );
 
-    println!("{}", data.data_to_string());
-    // TODO: Send error to frontend
-    debug_services::execute_command(data.data_to_string().as_str()).unwrap();
-}
+        let command = data
+            .data_to_string()
+            .map_err(|err| Error::invalid_argument(&err.to_string()))?;
 
-pub fn push_set_view_port_size_request_command(size: Vec2i) {
-    debug_services::timed_block!("push_set_view_port_size");
+        log::debug!("{}", command);
 
-    match get_application_state().as_mut() {
-        Some(application_state) => {
-            let mut state = application_state
-                .world
-                .resources
-                .get_mut::<CommandsState>()
-                .expect("failed to get commands state");
+        debug_services::execute_command(&mut application_state.world, command.as_str())
+            .map_err(|err| Error::command_failed(&err))
+    })
+}
 
-            push_request_command_data(
-                &mut state,
-                RequestCommandType::PushVec2i,
-                CommandData::vec2i(size),
-            );
-            push_request_command(&mut state, RequestCommandType::SetViewportSize);
-        }
-        None => {
-            panic!("failed to get application state");
-        }
-    }
+pub fn push_set_view_port_size_request_command(
+    application_state: &mut ApplicationState,
+    size: Vec2i,
+) {
+    let _block = debug_services::timed_block!(
+        profile_sink(&application_state.world),
+        "push_set_view_port_size"
+    );
+
+    push_request_commands(
+        application_state,
+        &[
+            RequestCommand {
+                command_type: RequestCommandType::PushVec2i,
+                data: CommandData::vec2i(size),
+            },
+            RequestCommand {
+                command_type: RequestCommandType::SetViewportSize,
+                data: CommandData::default(),
+            },
+        ],
+    );
 }
 
 // TODO: Use commands
-pub fn push_text_size(size: Vec2f) {
-    debug_services::timed_block!("push_text_size");
+pub fn push_text_size(application_state: &mut ApplicationState, size: Vec2f) {
+    let _block =
+        debug_services::timed_block!(profile_sink(&application_state.world), "push_text_size");
+    record_session_event(application_state, SessionEvent::TextSize { size });
+
+    let mut state = application_state
+        .world
+        .resources
+        .get_mut::<RenderState>()
+        .expect("failed to get render state");
 
-    let state = &mut RENDER_STATE.lock().expect("failed to get render state");
     state.push(size);
 }
 
-pub fn push_on_touch_start_request_command(point: Vec2f) {
-    debug_services::timed_block!("push_set_view_port_size");
+pub fn push_on_touch_start_request_command(
+    application_state: &mut ApplicationState,
+    time_offset: i32,
+    pointer_id: i32,
+    point: Vec2f,
+) {
+    let _block = debug_services::timed_block!(
+        profile_sink(&application_state.world),
+        "push_on_touch_start"
+    );
 
-    match get_application_state().as_mut() {
-        Some(application_state) => {
-            let mut state = application_state
-                .world
-                .resources
-                .get_mut::<CommandsState>()
-                .expect("failed to get commands state");
+    push_request_commands(
+        application_state,
+        &[
+            RequestCommand {
+                command_type: RequestCommandType::PushInt32,
+                data: CommandData::int32(time_offset),
+            },
+            RequestCommand {
+                command_type: RequestCommandType::PushInt32,
+                data: CommandData::int32(pointer_id),
+            },
+            RequestCommand {
+                command_type: RequestCommandType::PushVec2f,
+                data: CommandData::vec2f(point),
+            },
+            RequestCommand {
+                command_type: RequestCommandType::OnTouchStart,
+                data: CommandData::default(),
+            },
+        ],
+    );
+}
 
-            push_request_command_data(
-                &mut state,
-                RequestCommandType::PushVec2f,
-                CommandData::vec2f(point),
-            );
-            push_request_command(&mut state, RequestCommandType::OnTouchStart);
-        }
-        None => {
-            panic!("failed to get application state");
-        }
-    }
+pub fn push_on_touch_end_request_command(
+    application_state: &mut ApplicationState,
+    time_offset: i32,
+    pointer_id: i32,
+    point: Vec2f,
+) {
+    let _block =
+        debug_services::timed_block!(profile_sink(&application_state.world), "push_on_touch_end");
+
+    push_request_commands(
+        application_state,
+        &[
+            RequestCommand {
+                command_type: RequestCommandType::PushInt32,
+                data: CommandData::int32(time_offset),
+            },
+            RequestCommand {
+                command_type: RequestCommandType::PushInt32,
+                data: CommandData::int32(pointer_id),
+            },
+            RequestCommand {
+                command_type: RequestCommandType::PushVec2f,
+                data: CommandData::vec2f(point),
+            },
+            RequestCommand {
+                command_type: RequestCommandType::OnTouchEnd,
+                data: CommandData::default(),
+            },
+        ],
+    );
 }
 
-pub fn push_on_touch_end_request_command(point: Vec2f) {
-    debug_services::timed_block!("push_set_view_port_size");
+pub fn push_on_touch_move_request_command(
+    application_state: &mut ApplicationState,
+    time_offset: i32,
+    pointer_id: i32,
+    point: Vec2f,
+) {
+    let _block =
+        debug_services::timed_block!(profile_sink(&application_state.world), "push_on_touch_move");
+
+    push_request_commands(
+        application_state,
+        &[
+            RequestCommand {
+                command_type: RequestCommandType::PushInt32,
+                data: CommandData::int32(time_offset),
+            },
+            RequestCommand {
+                command_type: RequestCommandType::PushInt32,
+                data: CommandData::int32(pointer_id),
+            },
+            RequestCommand {
+                command_type: RequestCommandType::PushVec2f,
+                data: CommandData::vec2f(point),
+            },
+            RequestCommand {
+                command_type: RequestCommandType::OnTouchMove,
+                data: CommandData::default(),
+            },
+        ],
+    );
+}
 
-    match get_application_state().as_mut() {
-        Some(application_state) => {
-            let mut state = application_state
-                .world
-                .resources
-                .get_mut::<CommandsState>()
-                .expect("failed to get commands state");
+pub fn push_on_key_down_request_command(
+    application_state: &mut ApplicationState,
+    key_code: i32,
+    modifiers: i32,
+) {
+    let _block =
+        debug_services::timed_block!(profile_sink(&application_state.world), "push_on_key_down");
+
+    push_request_commands(
+        application_state,
+        &[
+            RequestCommand {
+                command_type: RequestCommandType::PushInt32,
+                data: CommandData::int32(key_code),
+            },
+            RequestCommand {
+                command_type: RequestCommandType::PushInt32,
+                data: CommandData::int32(modifiers),
+            },
+            RequestCommand {
+                command_type: RequestCommandType::OnKeyDown,
+                data: CommandData::default(),
+            },
+        ],
+    );
+}
 
-            push_request_command_data(
-                &mut state,
-                RequestCommandType::PushVec2f,
-                CommandData::vec2f(point),
-            );
-            push_request_command(&mut state, RequestCommandType::OnTouchEnd);
-        }
-        None => {
-            panic!("failed to get application state");
-        }
-    }
+pub fn push_on_key_up_request_command(
+    application_state: &mut ApplicationState,
+    key_code: i32,
+    modifiers: i32,
+) {
+    let _block =
+        debug_services::timed_block!(profile_sink(&application_state.world), "push_on_key_up");
+
+    push_request_commands(
+        application_state,
+        &[
+            RequestCommand {
+                command_type: RequestCommandType::PushInt32,
+                data: CommandData::int32(key_code),
+            },
+            RequestCommand {
+                command_type: RequestCommandType::PushInt32,
+                data: CommandData::int32(modifiers),
+            },
+            RequestCommand {
+                command_type: RequestCommandType::OnKeyUp,
+                data: CommandData::default(),
+            },
+        ],
+    );
 }
 
-pub fn push_on_touch_move_request_command(point: Vec2f) {
-    debug_services::timed_block!("push_set_view_port_size");
+pub fn push_on_text_input_request_command(application_state: &mut ApplicationState, text: &str) {
+    let _block =
+        debug_services::timed_block!(profile_sink(&application_state.world), "push_on_text_input");
 
-    match get_application_state().as_mut() {
-        Some(application_state) => {
-            let mut state = application_state
-                .world
-                .resources
-                .get_mut::<CommandsState>()
-                .expect("failed to get commands state");
+    push_request_commands(
+        application_state,
+        &[RequestCommand {
+            command_type: RequestCommandType::OnTextInput,
+            data: CommandData::string(text),
+        }],
+    );
+}
 
-            push_request_command_data(
-                &mut state,
-                RequestCommandType::PushVec2f,
-                CommandData::vec2f(point),
-            );
-            push_request_command(&mut state, RequestCommandType::OnTouchMove);
-        }
-        None => {
-            panic!("failed to get application state");
-        }
-    }
+pub fn push_on_scroll_request_command(
+    application_state: &mut ApplicationState,
+    delta: Vec2f,
+    modifiers: i32,
+) {
+    let _block =
+        debug_services::timed_block!(profile_sink(&application_state.world), "push_on_scroll");
+
+    push_request_commands(
+        application_state,
+        &[
+            RequestCommand {
+                command_type: RequestCommandType::PushVec2f,
+                data: CommandData::vec2f(delta),
+            },
+            RequestCommand {
+                command_type: RequestCommandType::PushInt32,
+                data: CommandData::int32(modifiers),
+            },
+            RequestCommand {
+                command_type: RequestCommandType::OnScroll,
+                data: CommandData::default(),
+            },
+        ],
+    );
+}
+
+pub fn push_camera_fit_request_command(application_state: &mut ApplicationState, camera_id: i32) {
+    let _block =
+        debug_services::timed_block!(profile_sink(&application_state.world), "push_camera_fit");
+
+    push_request_commands(
+        application_state,
+        &[
+            RequestCommand {
+                command_type: RequestCommandType::PushInt32,
+                data: CommandData::int32(camera_id),
+            },
+            RequestCommand {
+                command_type: RequestCommandType::CameraFit,
+                data: CommandData::default(),
+            },
+        ],
+    );
+}
+
+pub fn push_camera_go_to_request_command(
+    application_state: &mut ApplicationState,
+    camera_id: i32,
+    point: Vec2f,
+) {
+    let _block =
+        debug_services::timed_block!(profile_sink(&application_state.world), "push_camera_go_to");
+
+    push_request_commands(
+        application_state,
+        &[
+            RequestCommand {
+                command_type: RequestCommandType::PushInt32,
+                data: CommandData::int32(camera_id),
+            },
+            RequestCommand {
+                command_type: RequestCommandType::PushVec2f,
+                data: CommandData::vec2f(point),
+            },
+            RequestCommand {
+                command_type: RequestCommandType::CameraGoTo,
+                data: CommandData::default(),
+            },
+        ],
+    );
+}
+
+pub fn push_camera_save_bookmark_request_command(
+    application_state: &mut ApplicationState,
+    camera_id: i32,
+    name: &str,
+) {
+    let _block = debug_services::timed_block!(
+        profile_sink(&application_state.world),
+        "push_camera_save_bookmark"
+    );
+
+    push_request_commands(
+        application_state,
+        &[
+            RequestCommand {
+                command_type: RequestCommandType::PushInt32,
+                data: CommandData::int32(camera_id),
+            },
+            RequestCommand {
+                command_type: RequestCommandType::CameraSaveBookmark,
+                data: CommandData::string(name),
+            },
+        ],
+    );
+}
+
+pub fn push_camera_load_bookmark_request_command(
+    application_state: &mut ApplicationState,
+    camera_id: i32,
+    name: &str,
+) {
+    let _block = debug_services::timed_block!(
+        profile_sink(&application_state.world),
+        "push_camera_load_bookmark"
+    );
+
+    push_request_commands(
+        application_state,
+        &[
+            RequestCommand {
+                command_type: RequestCommandType::PushInt32,
+                data: CommandData::int32(camera_id),
+            },
+            RequestCommand {
+                command_type: RequestCommandType::CameraLoadBookmark,
+                data: CommandData::string(name),
+            },
+        ],
+    );
 }
 
 /// # Safety
 ///
-/// TODO: Doc
+/// `application_state` must be null or a handle returned by `init_world`
+/// that hasn't been destroyed yet, `data` must point to `length` request commands.
 #[no_mangle]
-pub unsafe extern "C" fn c_send_request_commands(data: *const RequestCommand, length: c_int) {
-    debug_services::timed_block!("c_send_request_commands");
-
-    match get_application_state().as_mut() {
-        Some(application_state) => {
-            let mut state = application_state
-                .world
-                .resources
-                .get_mut::<CommandsState>()
-                .expect("failed to get commands state");
+pub unsafe extern "C" fn c_send_request_commands(
+    application_state: *mut ApplicationState,
+    data: *const RequestCommand,
+    length: c_int,
+) -> StatusCode {
+    catch_errors(|| {
+        let application_state = get_application_state(application_state)?;
+        let _block = debug_services::timed_block!(
+            profile_sink(&application_state.world),
+            "c_send_request_commands"
+        );
 
-            let requests = slice::from_raw_parts(data, length as usize);
-            state.request_commands.extend_from_slice(requests);
+        if length == 0 {
+            return Ok(());
         }
-        None => {
-            panic!("failed to get application state");
+
+        if data.is_null() || length < 0 {
+            return Err(Error::invalid_argument("invalid request commands buffer"));
         }
-    }
+
+        let requests = slice::from_raw_parts(data, length as usize);
+        push_request_commands(application_state, requests);
+
+        Ok(())
+    })
 }
 
+/// # Safety
+///
+/// `application_state` must be null or a handle returned by `init_world`
+/// that hasn't been destroyed yet.
+/// `data` must be null or valid for writes.
 #[no_mangle]
-pub extern "C" fn get_render_commands(format: SerializeFormat) -> RawBuffer {
-    debug_services::timed_block!("get_render_commands");
+pub unsafe extern "C" fn get_render_commands(
+    application_state: *mut ApplicationState,
+    format: SerializeFormat,
+    data: *mut RawBuffer,
+) -> StatusCode {
+    catch_errors(|| {
+        let application_state = get_application_state(application_state)?;
+        let _block = debug_services::timed_block!(
+            profile_sink(&application_state.world),
+            "get_render_commands"
+        );
 
-    match get_application_state().as_mut() {
-        Some(application_state) => {
-            let state = application_state
-                .world
-                .resources
-                .get::<CommandsState>()
-                .expect("failed to get commands state");
+        let state = application_state
+            .world
+            .resources
+            .get::<CommandsState>()
+            .expect("failed to get commands state");
 
-            let memory = &mut memory::get_memory_state();
+        let memory = &mut application_state.memory_state;
 
-            match format {
-                SerializeFormat::Json => {
-                    serialize_json_render_commands(memory, &state.render_commands)
-                }
+        let buffer = match format {
+            SerializeFormat::Json => serialize_json_render_commands(memory, &state.render_commands),
+            SerializeFormat::FlatBuffers => {
+                serialize_flatbuffers_render_commands(memory, &state.render_commands)
             }
-        }
-        None => {
-            panic!("failed to get application state");
-        }
-    }
+            SerializeFormat::CapnProto => {
+                serialize_capnp_render_commands(memory, &state.render_commands)
+            }
+        };
+
+        let buffer = buffer.map_err(|err| {
+            Error::serialization_failed(&format!("failed to serialize render commands: {}", err))
+        })?;
+
+        write_result(data, buffer)
+    })
 }
 
+/// # Safety
+///
+/// `application_state` must be null or a handle returned by `init_world`
+/// that hasn't been destroyed yet.
+/// `data` must be null or valid for writes.
 #[no_mangle]
-pub extern "C" fn get_exec_commands_ser(format: SerializeFormat) -> RawBuffer {
-    debug_services::timed_block!("get_exec_commands");
+pub unsafe extern "C" fn get_exec_commands_ser(
+    application_state: *mut ApplicationState,
+    format: SerializeFormat,
+    data: *mut RawBuffer,
+) -> StatusCode {
+    catch_errors(|| {
+        let application_state = get_application_state(application_state)?;
+        let _block = debug_services::timed_block!(
+            profile_sink(&application_state.world),
+            "get_exec_commands"
+        );
 
-    match get_application_state().as_mut() {
-        Some(application_state) => {
-            let state = application_state
-                .world
-                .resources
-                .get::<CommandsState>()
-                .expect("failed to get commands state");
+        let state = application_state
+            .world
+            .resources
+            .get::<CommandsState>()
+            .expect("failed to get commands state");
 
-            let memory = &mut memory::get_memory_state();
+        let memory = &mut application_state.memory_state;
 
-            match format {
-                SerializeFormat::Json => serialize_json_exec_commands(memory, &state.exec_commands),
+        let buffer = match format {
+            SerializeFormat::Json => serialize_json_exec_commands(memory, &state.exec_commands),
+            SerializeFormat::FlatBuffers => {
+                serialize_flatbuffers_exec_commands(memory, &state.exec_commands)
             }
-        }
-        None => {
-            panic!("failed to get application state");
-        }
-    }
+            SerializeFormat::CapnProto => {
+                serialize_capnp_exec_commands(memory, &state.exec_commands)
+            }
+        };
+
+        let buffer = buffer.map_err(|err| {
+            Error::serialization_failed(&format!("failed to serialize execution commands: {}", err))
+        })?;
+
+        write_result(data, buffer)
+    })
 }
 
+/// # Safety
+///
+/// `application_state` must be null or a handle returned by `init_world`
+/// that hasn't been destroyed yet.
 #[no_mangle]
-pub extern "C" fn send_request_commands(format: SerializeFormat, data: RawBuffer) {
-    debug_services::timed_block!("send_request_commands");
+pub unsafe extern "C" fn send_request_commands(
+    application_state: *mut ApplicationState,
+    format: SerializeFormat,
+    data: RawBuffer,
+) -> StatusCode {
+    catch_errors(|| {
+        let application_state = get_application_state(application_state)?;
+        let _block = debug_services::timed_block!(
+            profile_sink(&application_state.world),
+            "send_request_commands"
+        );
 
-    match get_application_state().as_mut() {
-        Some(application_state) => {
-            let mut state = application_state
-                .world
-                .resources
-                .get_mut::<CommandsState>()
-                .expect("failed to get application state");
+        let memory = &mut application_state.memory_state;
 
-            let requests = match format {
-                SerializeFormat::Json => deserialize_json_request_commands(data),
-            };
+        let requests = match format {
+            SerializeFormat::Json => deserialize_json_request_commands(memory, data),
+            SerializeFormat::FlatBuffers => deserialize_flatbuffers_request_commands(memory, data),
+            SerializeFormat::CapnProto => deserialize_capnp_request_commands(memory, data),
+        };
 
-            match requests {
-                Ok(data) => {
-                    state.request_commands.extend(data.into_iter());
-                }
-                Err(err) => {
-                    log::warn!("failed to deserialize request commands: {}", err);
-                }
+        match requests {
+            Ok(data) => {
+                push_request_commands(application_state, &data);
+                Ok(())
             }
+            Err(err) => Err(Error::invalid_argument(&format!(
+                "failed to deserialize request commands: {}",
+                err
+            ))),
         }
-        None => {
-            panic!("failed to get application state");
-        }
+    })
+}
+
+fn push_request_commands(application_state: &mut ApplicationState, requests: &[RequestCommand]) {
+    if let Some(recorder) = &mut application_state.session_recorder {
+        recorder.try_record(SessionEvent::request_commands(requests));
     }
+
+    let mut state = application_state
+        .world
+        .resources
+        .get_mut::<CommandsState>()
+        .expect("failed to get commands state");
+
+    state.request_commands.extend_from_slice(requests);
 }
 
 fn set_view_port_size(world: &mut World, width: i32, height: i32) {
@@ -756,96 +1614,185 @@ fn set_view_port_size(world: &mut World, width: i32, height: i32) {
     view_port_size.height = height;
 }
 
-fn on_touch_start(world: &mut World, touch: Vec2f) {
-    {
-        let mut touch_state = world
-            .resources
-            .get_mut::<TouchState>()
-            .expect("falied to get viewport");
+fn pop_camera_id(memory: &mut CommandsDataMemory) -> usize {
+    match memory.int32_data.pop() {
+        Some(camera_id) => camera_id.max(0) as usize,
+        None => gapi::CAMERA_ORTHO,
+    }
+}
+
+/// The optional time offset is in milliseconds relative to the frame time,
+/// so the host doesn't need to know the epoch of the frame clock and the offsets
+/// stay far from the i32 range.
+fn pop_touch_time(world: &World, memory: &mut CommandsDataMemory) -> Duration {
+    let frame_time = world
+        .resources
+        .get::<FrameClock>()
+        .expect("failed to get frame clock")
+        .time;
 
-        touch_state.touch = Touch::Start;
-        touch_state.touch_start = touch;
-        touch_state.touch_current = touch;
+    match memory.int32_data.pop() {
+        Some(offset) if offset < 0 => {
+            frame_time.saturating_sub(Duration::from_millis(offset.unsigned_abs() as u64))
+        }
+        Some(offset) => frame_time + Duration::from_millis(offset as u64),
+        None => frame_time,
     }
+}
+
+fn on_touch_start(world: &mut World, pointer_id: i32, touch: Vec2f, time: Duration) {
+    world
+        .resources
+        .get_mut::<TouchState>()
+        .expect("failed to get touch state")
+        .on_touch_start(pointer_id, touch, time);
 
     let query = <(Write<TouchState>,)>::query();
 
     for (mut touch_state,) in query.iter(world) {
-        touch_state.touch = Touch::Start;
-        touch_state.touch_start = touch;
-        touch_state.touch_current = touch;
+        touch_state.on_touch_start(pointer_id, touch, time);
     }
 }
 
-fn on_touch_end(world: &mut World, touch: Vec2f) {
-    {
-        let mut touch_state = world
-            .resources
-            .get_mut::<TouchState>()
-            .expect("falied to get viewport");
+fn on_touch_end(world: &mut World, pointer_id: i32, touch: Vec2f, time: Duration) {
+    world
+        .resources
+        .get_mut::<TouchState>()
+        .expect("failed to get touch state")
+        .on_touch_end(pointer_id, touch, time);
 
-        touch_state.touch = Touch::End;
-        touch_state.touch_current = touch;
+    let query = <(Write<TouchState>,)>::query();
+
+    for (mut touch_state,) in query.iter(world) {
+        touch_state.on_touch_end(pointer_id, touch, time);
     }
+}
+
+fn on_touch_move(world: &mut World, pointer_id: i32, touch: Vec2f, time: Duration) {
+    world
+        .resources
+        .get_mut::<TouchState>()
+        .expect("failed to get touch state")
+        .on_touch_move(pointer_id, touch, time);
 
     let query = <(Write<TouchState>,)>::query();
 
     for (mut touch_state,) in query.iter(world) {
-        if touch_state.touch == Touch::Start || touch_state.touch == Touch::Move {
-            touch_state.touch = Touch::End;
-            touch_state.touch_current = touch;
-        }
+        touch_state.on_touch_move(pointer_id, touch, time);
     }
 }
 
-fn on_touch_move(world: &mut World, touch: Vec2f) {
-    {
-        let mut touch_state = world
-            .resources
-            .get_mut::<TouchState>()
-            .expect("falied to get viewport");
+fn on_key_down(world: &mut World, key_code: i32, modifiers: i32) {
+    let mut keyboard_state = world
+        .resources
+        .get_mut::<KeyboardState>()
+        .expect("failed to get keyboard state");
+
+    keyboard_state.modifiers = modifiers;
 
-        touch_state.pos = touch;
-        touch_state.touch = Touch::Move;
-        touch_state.touch_current = touch;
+    // NOTE: Repeated key downs while the key is held don't produce new edges.
+    if !keyboard_state.is_key_down(key_code) {
+        keyboard_state.keys_down.push(key_code);
+        keyboard_state.pressed.push(key_code);
     }
+}
 
-    let query = <(Write<TouchState>,)>::query();
+fn on_key_up(world: &mut World, key_code: i32, modifiers: i32) {
+    let mut keyboard_state = world
+        .resources
+        .get_mut::<KeyboardState>()
+        .expect("failed to get keyboard state");
 
-    for (mut touch_state,) in query.iter(world) {
-        touch_state.pos = touch;
+    keyboard_state.modifiers = modifiers;
+    keyboard_state.keys_down.retain(|key| *key != key_code);
+    keyboard_state.released.push(key_code);
+}
+
+fn on_text_input(world: &mut World, text: &str) {
+    let mut keyboard_state = world
+        .resources
+        .get_mut::<KeyboardState>()
+        .expect("failed to get keyboard state");
+
+    keyboard_state.text_input.push_str(text);
+}
+
+fn on_scroll(world: &mut World, delta: Vec2f, modifiers: i32) {
+    let mut scroll_state = world
+        .resources
+        .get_mut::<ScrollState>()
+        .expect("failed to get scroll state");
+
+    scroll_state.delta += delta;
+    scroll_state.modifiers = modifiers;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ptr;
+
+    use crate::commands::Vec2f;
+    use crate::components::ViewPortSize;
+    use crate::errors::StatusCode;
+    use crate::test_utils::{last_error, TestWorld};
+    use crate::RawBuffer;
+
+    #[test]
+    fn worlds_are_isolated() {
+        let mut first = TestWorld::with_view_port(100, 200);
+        let mut second = TestWorld::new();
+
+        crate::push_on_touch_start_request_command(second.state(), 0, 0, Vec2f::new(10.0, 10.0));
+
+        first.run_frame();
+        second.run_frame();
+
+        let first_state = first.state();
+        let second_state = second.state();
 
-        if touch_state.touch == Touch::Start || touch_state.touch == Touch::Move {
-            touch_state.touch = Touch::Move;
-            touch_state.touch_current = touch;
+        let first_view_port = first_state.world.resources.get::<ViewPortSize>().unwrap();
+        let second_view_port = second_state.world.resources.get::<ViewPortSize>().unwrap();
+
+        assert_eq!(100, first_view_port.width);
+        assert_eq!(200, first_view_port.height);
+        assert_eq!(0, second_view_port.width);
+        assert_eq!(0, second_view_port.height);
+    }
+
+    #[test]
+    fn invalid_handle() {
+        assert_eq!(StatusCode::InvalidHandle, unsafe {
+            crate::step(ptr::null_mut())
+        });
+        assert_eq!("invalid application state handle", last_error());
+    }
+
+    #[test]
+    fn execute_command_failed() {
+        let mut world = TestWorld::new();
+
+        assert_eq!(StatusCode::CommandFailed, world.execute("math::unknown 2"));
+        assert_eq!("Command 'math::unknown' not found", last_error());
+
+        let command = [0xff, 0xfe];
+        let status =
+            unsafe { crate::c_execute_command(world.handle, RawBuffer::from_bytes(&command)) };
+
+        assert_eq!(StatusCode::InvalidArgument, status);
+    }
+
+    #[test]
+    fn panics_are_caught() {
+        let mut world = TestWorld::new();
+
+        {
+            let state = world.state();
+            state.world.resources.remove::<ViewPortSize>();
         }
+
+        assert_eq!(StatusCode::Panic, unsafe {
+            crate::render_pass1(world.handle)
+        });
+        assert_eq!("failed to get view port size", last_error());
     }
 }
-
-// #[cfg(test)]
-// mod tests {
-//     #[test]
-//     fn it_works() {
-//         crate::init_world();
-//         let json = "[{\"SetViewportSize\": {\"width: 100, \"height\": 200}}]".as_bytes();
-//         let data = crate::RawBuffer {
-//             data: json.as_ptr(),
-//             length: json.len(),
-//         };
-//         crate::send_request_commands(crate::SerializeFormat::Json, data);
-//         // crate::step();
-//         // let mut commands_state = crate::commands::CommandsState::default();
-//         // crate::render::gapi::push_color_shader(&mut commands_state);
-//         let data = crate::get_render_commands(crate::SerializeFormat::Json);
-//         println!("{:?}", data);
-//         log::error!("Commencing yak shaving");
-//         panic!(":(");
-//         // crate::get_render_commands();
-//         // crate::step();
-//         // let data = crate::get_render_commands();
-
-//         // dbg!(data);
-
-//         assert_eq!(2 + 2, 4);
-//     }
-// }
@@ -0,0 +1,127 @@
+use legion::prelude::*;
+
+use crate::commands::Vec2f;
+use crate::components::{Bounds, Camera2DPositionListener, CameraRegistry};
+
+/// Entity with the greatest `z_order` among `hits`, ties go to the first one.
+pub fn topmost<'a, I>(hits: I) -> Option<Entity>
+where
+    I: Iterator<Item = (Entity, &'a Bounds)>,
+{
+    let mut result: Option<(Entity, i32)> = None;
+
+    for (entity, bounds) in hits {
+        match result {
+            Some((_, z_order)) if z_order >= bounds.z_order => {}
+            _ => result = Some((entity, bounds.z_order)),
+        }
+    }
+
+    result.map(|(entity, _)| entity)
+}
+
+/// Whether the screen `point` is inside of the camera view port and of `bounds`
+/// in the world space of the camera.
+pub fn hit_test(
+    camera_registry: &CameraRegistry,
+    camera_id: usize,
+    bounds: &Bounds,
+    point: Vec2f,
+) -> bool {
+    camera_registry.contains_screen_point(camera_id, point)
+        && camera_registry
+            .screen_to_world(camera_id, point)
+            .is_some_and(|point| bounds.rect.contains(point))
+}
+
+/// Returns the topmost entity of the camera with `camera_id` under the screen `point`,
+/// nothing is picked outside of the camera view port.
+pub fn pick_entity(world: &mut World, camera_id: usize, point: Vec2f) -> Option<Entity> {
+    let camera_registry = world
+        .resources
+        .get::<CameraRegistry>()
+        .expect("failed to get camera registry")
+        .clone();
+
+    let hits = <(Read<Bounds>, Read<Camera2DPositionListener>)>::query()
+        .iter_entities(world)
+        .filter(|(_, (bounds, camera))| {
+            camera.id == camera_id && hit_test(&camera_registry, camera_id, bounds, point)
+        })
+        .map(|(entity, (bounds, _))| (entity, *bounds))
+        .collect::<Vec<(Entity, Bounds)>>();
+
+    topmost(hits.iter().map(|(entity, bounds)| (*entity, bounds)))
+}
+
+#[cfg(test)]
+mod tests {
+    use legion::prelude::*;
+
+    use crate::commands::{Rect, Vec2f};
+    use crate::components::{
+        Bounds, Camera2D, Camera2DPositionListener, CameraRegistry, ViewPortSize, YAxis,
+    };
+    use crate::picking::pick_entity;
+
+    fn insert_bounds(world: &mut World, camera_id: usize, rect: Rect, z_order: i32) -> Entity {
+        world.insert(
+            (),
+            vec![(
+                Bounds { rect, z_order },
+                Camera2DPositionListener::new(camera_id),
+            )],
+        )[0]
+    }
+
+    #[test]
+    fn pick_topmost_entity() {
+        let mut world = Universe::new().create_world();
+
+        let mut camera_registry = CameraRegistry::default();
+        camera_registry.update_camera(
+            &Camera2D {
+                id: 1,
+                pos: Vec2f::new(-100.0, -50.0),
+                zoom: 2.0,
+                rotation: 0.0,
+                y_axis: YAxis::Down,
+            },
+            &ViewPortSize {
+                width: 200,
+                height: 100,
+            },
+        );
+        world.resources.insert(camera_registry);
+
+        let back = insert_bounds(
+            &mut world,
+            1,
+            Rect::new(Vec2f::ZERO, Vec2f::new(40.0, 40.0)),
+            0,
+        );
+        let front = insert_bounds(
+            &mut world,
+            1,
+            Rect::new(Vec2f::new(10.0, 10.0), Vec2f::new(10.0, 10.0)),
+            1,
+        );
+        insert_bounds(
+            &mut world,
+            0,
+            Rect::new(Vec2f::ZERO, Vec2f::new(40.0, 40.0)),
+            2,
+        );
+
+        assert_eq!(
+            Some(back),
+            pick_entity(&mut world, 1, Vec2f::new(10.0, 10.0))
+        );
+        assert_eq!(
+            Some(front),
+            pick_entity(&mut world, 1, Vec2f::new(30.0, 30.0))
+        );
+        assert_eq!(None, pick_entity(&mut world, 1, Vec2f::new(90.0, 90.0)));
+        assert_eq!(None, pick_entity(&mut world, 2, Vec2f::new(10.0, 10.0)));
+    }
+}
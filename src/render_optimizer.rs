@@ -0,0 +1,257 @@
+use crate::commands::{Color, RenderCommand, RenderCommandType};
+use crate::debug_services::profile::RenderStreamStats;
+
+/// Runs `optimize` over the render commands at the end of the frame.
+#[derive(Debug)]
+pub struct RenderOptimizerState {
+    pub is_enabled: bool,
+}
+
+impl Default for RenderOptimizerState {
+    fn default() -> Self {
+        RenderOptimizerState { is_enabled: true }
+    }
+}
+
+/// Removes the commands that don't change what the host renders. The host is a stack
+/// machine: the push commands put data on the stacks, `SetColorUniform` pops a color,
+/// `SetCamera` pops an int32 and the draws consume everything pushed for them since
+/// the previous draw, the vertices for the shapes and the positions and strings for text.
+///
+/// - Shader, uniform and camera changes to the current value are dropped together
+///   with the data they would pop. Selecting a shader resets the known uniform.
+/// - Draws without data are dropped.
+/// - A draw followed by the same draw with only data pushes between them is dropped,
+///   the second one draws the data of both.
+pub fn optimize(commands: &mut Vec<RenderCommand>) -> RenderStreamStats {
+    let mut stats = RenderStreamStats {
+        commands_before: commands.len(),
+        ..RenderStreamStats::default()
+    };
+
+    let mut keep = vec![true; commands.len()];
+    let mut shader: Option<RenderCommandType> = None;
+    let mut uniform: Option<Color> = None;
+    let mut camera: Option<i32> = None;
+    let mut colors: Vec<(usize, Color)> = Vec::new();
+    let mut ints: Vec<(usize, i32)> = Vec::new();
+    let mut vertices = 0;
+    let mut strings = 0;
+    let mut last_draw: Option<(usize, RenderCommandType)> = None;
+
+    for (i, command) in commands.iter().enumerate() {
+        match command.command_type {
+            RenderCommandType::PushColor => colors.push((i, command.data.color)),
+            RenderCommandType::PushInt32 => ints.push((i, command.data.int32)),
+            RenderCommandType::PushVec2f => vertices += 1,
+            RenderCommandType::PushString => strings += 1,
+            RenderCommandType::PushColorShader | RenderCommandType::PushTextShader => {
+                if shader == Some(command.command_type) {
+                    keep[i] = false;
+                    stats.redundant_states += 1;
+                }
+                else {
+                    shader = Some(command.command_type);
+                    uniform = None;
+                    last_draw = None;
+                }
+            }
+            RenderCommandType::SetColorUniform => match colors.pop() {
+                Some((color_index, color)) if uniform == Some(color) => {
+                    keep[i] = false;
+                    keep[color_index] = false;
+                    stats.redundant_states += 1;
+                }
+                popped => {
+                    uniform = popped.map(|(_, color)| color);
+                    last_draw = None;
+                }
+            },
+            RenderCommandType::SetCamera => match ints.pop() {
+                Some((id_index, id)) if camera == Some(id) => {
+                    keep[i] = false;
+                    keep[id_index] = false;
+                    stats.redundant_states += 1;
+                }
+                popped => {
+                    camera = popped.map(|(_, id)| id);
+                    last_draw = None;
+                }
+            },
+            RenderCommandType::DrawLines
+            | RenderCommandType::DrawPoints
+            | RenderCommandType::DrawQuads
+            | RenderCommandType::DrawText => {
+                let is_empty = if command.command_type == RenderCommandType::DrawText {
+                    strings == 0
+                }
+                else {
+                    vertices == 0
+                };
+
+                if is_empty {
+                    keep[i] = false;
+                    stats.empty_draws += 1;
+                    continue;
+                }
+
+                if let Some((draw_index, draw_type)) = last_draw {
+                    if draw_type == command.command_type {
+                        keep[draw_index] = false;
+                        stats.merged_draws += 1;
+                    }
+                }
+
+                last_draw = Some((i, command.command_type));
+                vertices = 0;
+                strings = 0;
+            }
+        }
+    }
+
+    let mut flags = keep.iter();
+    commands.retain(|_| *flags.next().unwrap());
+    stats.commands_after = commands.len();
+
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::{
+        Color, CommandData, CommandsState, RenderCommand, RenderCommandType, Vec2f,
+    };
+    use crate::debug_services::profile::ProfileState;
+    use crate::gapi;
+    use crate::render_optimizer::optimize;
+    use crate::render_validator::{validate, RenderStreamIssue};
+    use crate::test_utils::TestWorld;
+
+    fn command_types(commands: &[RenderCommand]) -> Vec<RenderCommandType> {
+        commands
+            .iter()
+            .map(|command| command.command_type)
+            .collect()
+    }
+
+    fn push_line(commands_state: &mut CommandsState, color: Color) {
+        gapi::push_color_shader(commands_state);
+        gapi::push_color(commands_state, color);
+        gapi::set_color_uniform(commands_state);
+        gapi::push_vec2f(commands_state, Vec2f::ZERO);
+        gapi::push_vec2f(commands_state, Vec2f::new(1.0, 1.0));
+        gapi::set_camera(commands_state, gapi::CAMERA_ORTHO);
+        gapi::draw_lines(commands_state);
+    }
+
+    #[test]
+    fn merge_batches_with_same_state() {
+        let mut commands_state = CommandsState::default();
+        let color = Color::rgb(1.0, 0.0, 0.0);
+
+        push_line(&mut commands_state, color);
+        push_line(&mut commands_state, color);
+
+        let stats = optimize(&mut commands_state.render_commands);
+
+        assert_eq!(
+            vec![
+                RenderCommandType::PushColorShader,
+                RenderCommandType::PushColor,
+                RenderCommandType::SetColorUniform,
+                RenderCommandType::PushVec2f,
+                RenderCommandType::PushVec2f,
+                RenderCommandType::PushInt32,
+                RenderCommandType::SetCamera,
+                RenderCommandType::PushVec2f,
+                RenderCommandType::PushVec2f,
+                RenderCommandType::DrawLines,
+            ],
+            command_types(&commands_state.render_commands)
+        );
+        assert_eq!(16, stats.commands_before);
+        assert_eq!(10, stats.commands_after);
+        assert_eq!(3, stats.redundant_states);
+        assert_eq!(1, stats.merged_draws);
+    }
+
+    #[test]
+    fn keep_state_changes() {
+        let mut commands_state = CommandsState::default();
+
+        push_line(&mut commands_state, Color::rgb(1.0, 0.0, 0.0));
+        push_line(&mut commands_state, Color::rgb(0.0, 1.0, 0.0));
+        gapi::set_camera(&mut commands_state, gapi::CAMERA_UI);
+        gapi::push_vec2f(&mut commands_state, Vec2f::ZERO);
+        gapi::push_vec2f(&mut commands_state, Vec2f::new(1.0, 1.0));
+        gapi::draw_lines(&mut commands_state);
+
+        let stats = optimize(&mut commands_state.render_commands);
+
+        assert_eq!(0, stats.merged_draws);
+        assert_eq!(2, stats.redundant_states);
+        assert_eq!(
+            3,
+            commands_state
+                .render_commands
+                .iter()
+                .filter(|command| command.command_type == RenderCommandType::DrawLines)
+                .count()
+        );
+    }
+
+    #[test]
+    fn drop_empty_draws() {
+        let mut commands = vec![
+            RenderCommand {
+                command_type: RenderCommandType::DrawLines,
+                data: CommandData::default(),
+            },
+            RenderCommand {
+                command_type: RenderCommandType::PushVec2f,
+                data: CommandData::vec2f(Vec2f::ZERO),
+            },
+            RenderCommand {
+                command_type: RenderCommandType::DrawText,
+                data: CommandData::default(),
+            },
+            RenderCommand {
+                command_type: RenderCommandType::DrawQuads,
+                data: CommandData::default(),
+            },
+        ];
+
+        let stats = optimize(&mut commands);
+
+        assert_eq!(
+            vec![RenderCommandType::PushVec2f, RenderCommandType::DrawQuads],
+            command_types(&commands)
+        );
+        assert_eq!(2, stats.empty_draws);
+    }
+
+    #[test]
+    fn optimize_frame() {
+        let mut world = TestWorld::with_view_port(640, 480);
+
+        // The camera registry has the view port since the first step.
+        world.step();
+        world.render(|state| {
+            let commands_state = state.world.resources.get::<CommandsState>().unwrap();
+            let profile_state = state.world.resources.get::<ProfileState>().unwrap();
+            let stats = profile_state.render_stream;
+
+            assert_eq!(commands_state.render_commands.len(), stats.commands_after);
+            assert!(stats.commands_before > stats.commands_after);
+            assert_eq!(
+                Vec::<RenderStreamIssue>::new(),
+                validate(&commands_state.render_commands)
+            );
+
+            #[cfg(debug_assertions)]
+            assert!(commands_state
+                .render_command_sources
+                .contains(&Some("grid_system")));
+        });
+    }
+}
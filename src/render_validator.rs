@@ -0,0 +1,195 @@
+use std::fmt;
+
+use crate::commands::{RenderCommand, RenderCommandType};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RenderStreamIssueKind {
+    DrawWithoutShader(RenderCommandType),
+    OddLinesVertices(usize),
+    QuadsWithoutPairs(usize),
+    SetCameraWithoutInt32,
+    SetColorUniformWithoutColor,
+    /// Values left on a stack after the last command, the command type is the push.
+    Leftover(RenderCommandType, usize),
+}
+
+/// A problem with the render commands, `command_index` points at the command that
+/// causes it, or at the first leftover push for `Leftover`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RenderStreamIssue {
+    pub command_index: usize,
+    pub kind: RenderStreamIssueKind,
+}
+
+impl fmt::Display for RenderStreamIssueKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RenderStreamIssueKind::DrawWithoutShader(command_type) => {
+                write!(f, "{:?} without a shader", command_type)
+            }
+            RenderStreamIssueKind::OddLinesVertices(count) => {
+                write!(f, "DrawLines with an odd number of vertices: {}", count)
+            }
+            RenderStreamIssueKind::QuadsWithoutPairs(count) => {
+                write!(f, "DrawQuads without pos/size pairs: {} vertices", count)
+            }
+            RenderStreamIssueKind::SetCameraWithoutInt32 => {
+                write!(f, "SetCamera without PushInt32")
+            }
+            RenderStreamIssueKind::SetColorUniformWithoutColor => {
+                write!(f, "SetColorUniform without PushColor")
+            }
+            RenderStreamIssueKind::Leftover(command_type, count) => {
+                write!(
+                    f,
+                    "{} {:?} left at the end of the frame",
+                    count, command_type
+                )
+            }
+        }
+    }
+}
+
+/// Simulates the host stacks the same way as `render_optimizer::optimize` and
+/// returns the commands the host can't execute or that leave values on the stacks.
+pub fn validate(commands: &[RenderCommand]) -> Vec<RenderStreamIssue> {
+    let mut issues = Vec::new();
+    let mut has_shader = false;
+    let mut colors: Vec<usize> = Vec::new();
+    let mut ints: Vec<usize> = Vec::new();
+    let mut vertices: Vec<usize> = Vec::new();
+    let mut strings: Vec<usize> = Vec::new();
+
+    let mut report = |command_index: usize, kind: RenderStreamIssueKind| {
+        issues.push(RenderStreamIssue {
+            command_index,
+            kind,
+        });
+    };
+
+    for (i, command) in commands.iter().enumerate() {
+        match command.command_type {
+            RenderCommandType::PushColor => colors.push(i),
+            RenderCommandType::PushInt32 => ints.push(i),
+            RenderCommandType::PushVec2f => vertices.push(i),
+            RenderCommandType::PushString => strings.push(i),
+            RenderCommandType::PushColorShader | RenderCommandType::PushTextShader => {
+                has_shader = true;
+            }
+            RenderCommandType::SetColorUniform => {
+                if colors.pop().is_none() {
+                    report(i, RenderStreamIssueKind::SetColorUniformWithoutColor);
+                }
+            }
+            RenderCommandType::SetCamera => {
+                if ints.pop().is_none() {
+                    report(i, RenderStreamIssueKind::SetCameraWithoutInt32);
+                }
+            }
+            RenderCommandType::DrawLines
+            | RenderCommandType::DrawPoints
+            | RenderCommandType::DrawQuads
+            | RenderCommandType::DrawText => {
+                if !has_shader {
+                    report(
+                        i,
+                        RenderStreamIssueKind::DrawWithoutShader(command.command_type),
+                    );
+                }
+
+                match command.command_type {
+                    RenderCommandType::DrawLines if vertices.len() % 2 == 1 => {
+                        report(i, RenderStreamIssueKind::OddLinesVertices(vertices.len()));
+                    }
+                    RenderCommandType::DrawQuads if vertices.len() % 2 == 1 => {
+                        report(i, RenderStreamIssueKind::QuadsWithoutPairs(vertices.len()));
+                    }
+                    _ => (),
+                }
+
+                vertices.clear();
+                strings.clear();
+            }
+        }
+    }
+
+    for (command_type, stack) in [
+        (RenderCommandType::PushColor, &colors),
+        (RenderCommandType::PushInt32, &ints),
+        (RenderCommandType::PushVec2f, &vertices),
+        (RenderCommandType::PushString, &strings),
+    ] {
+        if let Some(first) = stack.first() {
+            report(
+                *first,
+                RenderStreamIssueKind::Leftover(command_type, stack.len()),
+            );
+        }
+    }
+
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::{push_render_command, CommandsState, RenderCommandType, Vec2f};
+    use crate::gapi;
+    use crate::render_validator::{validate, RenderStreamIssue, RenderStreamIssueKind};
+
+    #[test]
+    fn valid_stream() {
+        let mut commands_state = CommandsState::default();
+
+        gapi::push_color_shader(&mut commands_state);
+        gapi::push_color_rgb(&mut commands_state, 1.0, 0.0, 0.0);
+        gapi::set_color_uniform(&mut commands_state);
+        gapi::push_quad_lines(&mut commands_state, Vec2f::ZERO, Vec2f::new(1.0, 1.0));
+        gapi::set_camera(&mut commands_state, gapi::CAMERA_UI);
+        gapi::draw_lines(&mut commands_state);
+
+        assert_eq!(
+            Vec::<RenderStreamIssue>::new(),
+            validate(&commands_state.render_commands)
+        );
+    }
+
+    #[test]
+    fn malformed_stream() {
+        let mut commands_state = CommandsState::default();
+
+        gapi::push_vec2f(&mut commands_state, Vec2f::ZERO);
+        gapi::draw_lines(&mut commands_state);
+        gapi::push_color_shader(&mut commands_state);
+        gapi::push_vec2f(&mut commands_state, Vec2f::ZERO);
+        gapi::draw_quads(&mut commands_state);
+        push_render_command(&mut commands_state, RenderCommandType::SetCamera);
+        gapi::push_vec2f(&mut commands_state, Vec2f::ZERO);
+        gapi::push_vec2f(&mut commands_state, Vec2f::ZERO);
+
+        assert_eq!(
+            vec![
+                RenderStreamIssue {
+                    command_index: 1,
+                    kind: RenderStreamIssueKind::DrawWithoutShader(RenderCommandType::DrawLines),
+                },
+                RenderStreamIssue {
+                    command_index: 1,
+                    kind: RenderStreamIssueKind::OddLinesVertices(1),
+                },
+                RenderStreamIssue {
+                    command_index: 4,
+                    kind: RenderStreamIssueKind::QuadsWithoutPairs(1),
+                },
+                RenderStreamIssue {
+                    command_index: 5,
+                    kind: RenderStreamIssueKind::SetCameraWithoutInt32,
+                },
+                RenderStreamIssue {
+                    command_index: 6,
+                    kind: RenderStreamIssueKind::Leftover(RenderCommandType::PushVec2f, 2),
+                },
+            ],
+            validate(&commands_state.render_commands)
+        );
+    }
+}
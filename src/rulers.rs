@@ -0,0 +1,267 @@
+use legion::prelude::*;
+
+use crate::commands::{CommandsState, Rect, Vec2f};
+use crate::components::{
+    Camera2DPositionListener, CameraRegistry, GridComponent, RulersState, TouchState,
+};
+use crate::debug_services;
+use crate::debug_services::profile::ProfileState;
+use crate::gapi;
+use crate::memory::MemoryState;
+use crate::render_state::RenderState;
+use crate::systems::grid::{grid_level, line_indices};
+
+/// Screen pixels between the labels, the labels of the major ticks that are
+/// closer are skipped.
+pub const RULER_LABEL_MIN_SPACING: f32 = 64.0;
+/// Part of the ruler thickness covered by the minor ticks.
+pub const RULER_MINOR_TICK: f32 = 0.3;
+
+/// The ticks of the rulers are aligned with the lines of the camera grid, the rulers
+/// aren't drawn for the cameras without `GridComponent`.
+pub fn render_rulers(world: &mut World, memory_state: &MemoryState) {
+    let rulers_state = *world
+        .resources
+        .get::<RulersState>()
+        .expect("failed to get rulers state");
+
+    if !rulers_state.is_enabled {
+        return;
+    }
+
+    let camera_id = rulers_state.camera_id;
+    let grid = <(Read<GridComponent>, Read<Camera2DPositionListener>)>::query()
+        .iter(world)
+        .find(|(_, camera)| camera.id == camera_id)
+        .map(|(grid, _)| *grid);
+
+    let grid = match grid {
+        Some(grid) if grid.step > 0 && grid.min_spacing > 0.0 => grid,
+        _ => return,
+    };
+
+    let resources = &world.resources;
+    let sink = resources
+        .get::<ProfileState>()
+        .expect("failed to get profile state")
+        .sink
+        .clone();
+    let _block = debug_services::timed_block!(sink, "render_rulers");
+
+    let camera_registry = resources
+        .get::<CameraRegistry>()
+        .expect("failed to get camera registry");
+
+    let (view_port, visible, units_per_pixel) = match (
+        camera_registry.get(camera_id),
+        camera_registry.visible_world_rect(camera_id),
+        camera_registry.screen_length_to_world(camera_id, 1.0),
+    ) {
+        (Some(camera), Some(visible), Some(units_per_pixel)) => {
+            (camera.view_port_rect, visible, units_per_pixel)
+        }
+        _ => return,
+    };
+
+    let commands_state = &mut resources
+        .get_mut::<CommandsState>()
+        .expect("failed to get commands state");
+
+    let render_state = &mut resources
+        .get_mut::<RenderState>()
+        .expect("failed to get render state");
+
+    let pointer = resources
+        .get::<TouchState>()
+        .expect("failed to get touch state")
+        .pos;
+
+    let thickness = rulers_state.thickness;
+    let top = Rect::new(view_port.pos, Vec2f::new(view_port.size.x, thickness));
+    let left = Rect::new(view_port.pos, Vec2f::new(thickness, view_port.size.y));
+    let to_screen = |point: Vec2f| {
+        camera_registry
+            .world_to_screen(camera_id, point)
+            .unwrap_or(Vec2f::ZERO)
+    };
+
+    let level = grid_level(&grid, 1.0 / units_per_pixel);
+    let major_spacing = level.major_step / units_per_pixel;
+    let label_every = (RULER_LABEL_MIN_SPACING / major_spacing).ceil().max(1.0) as i64;
+    let subdivisions = grid.subdivisions.max(2) as i64;
+
+    let mut ticks = Vec::new();
+    let mut labels = Vec::new();
+
+    for (step, length) in [
+        (level.minor_step, RULER_MINOR_TICK),
+        (level.major_step, 1.0),
+    ] {
+        let tick = thickness * length;
+        let is_major = length == 1.0;
+
+        // Same as the grid, faded out minor ticks are skipped and the major
+        // ticks aren't drawn twice.
+        if !is_major && level.fade == 0.0 {
+            continue;
+        }
+
+        let skip = |i: i64| !is_major && i % subdivisions == 0;
+
+        for i in line_indices(visible.pos.x, visible.pos.x + visible.size.x, step) {
+            if skip(i) {
+                continue;
+            }
+
+            let x = to_screen(Vec2f::new(i as f32 * step, 0.0)).x;
+
+            if x < top.pos.x + thickness || x > top.pos.x + top.size.x {
+                continue;
+            }
+
+            let bottom = top.pos.y + thickness;
+            ticks.push((Vec2f::new(x, bottom - tick), Vec2f::new(x, bottom)));
+
+            if is_major && i % label_every == 0 {
+                labels.push((tick_label(i, step), Vec2f::new(x + 2.0, top.pos.y)));
+            }
+        }
+
+        for i in line_indices(visible.pos.y, visible.pos.y + visible.size.y, step) {
+            if skip(i) {
+                continue;
+            }
+
+            let y = to_screen(Vec2f::new(0.0, i as f32 * step)).y;
+
+            if y < left.pos.y + thickness || y > left.pos.y + left.size.y {
+                continue;
+            }
+
+            let right = left.pos.x + thickness;
+            ticks.push((Vec2f::new(right - tick, y), Vec2f::new(right, y)));
+
+            if is_major && i % label_every == 0 {
+                labels.push((tick_label(i, step), Vec2f::new(left.pos.x, y + 2.0)));
+            }
+        }
+    }
+
+    gapi::set_camera(commands_state, gapi::CAMERA_UI);
+
+    // Background
+    gapi::push_color_shader(commands_state);
+    gapi::push_color(commands_state, rulers_state.background_color);
+    gapi::set_color_uniform(commands_state);
+    gapi::push_vec2f(commands_state, top.pos);
+    gapi::push_vec2f(commands_state, top.size);
+    gapi::push_vec2f(commands_state, left.pos);
+    gapi::push_vec2f(commands_state, left.size);
+    gapi::draw_quads(commands_state);
+
+    // Ticks
+    gapi::push_color(commands_state, rulers_state.tick_color);
+    gapi::set_color_uniform(commands_state);
+
+    for (from, to) in ticks {
+        gapi::push_vec2f(commands_state, from);
+        gapi::push_vec2f(commands_state, to);
+    }
+
+    gapi::draw_lines(commands_state);
+
+    // Pointer marker
+    if view_port.contains(pointer) {
+        gapi::push_color(commands_state, rulers_state.marker_color);
+        gapi::set_color_uniform(commands_state);
+        gapi::push_vec2f_xy(commands_state, pointer.x, top.pos.y);
+        gapi::push_vec2f_xy(commands_state, pointer.x, top.pos.y + thickness);
+        gapi::push_vec2f_xy(commands_state, left.pos.x, pointer.y);
+        gapi::push_vec2f_xy(commands_state, left.pos.x + thickness, pointer.y);
+        gapi::draw_lines(commands_state);
+    }
+
+    if labels.is_empty() {
+        return;
+    }
+
+    for (text, pos) in labels.iter() {
+        gapi::push_string_vec2f(commands_state, render_state, memory_state, text, *pos);
+    }
+
+    gapi::push_text_shader(commands_state);
+    gapi::push_color(commands_state, rulers_state.label_color);
+    gapi::set_color_uniform(commands_state);
+    gapi::draw_text(commands_state);
+}
+
+/// Label of the tick `i`, rounded to the decimals of `step` so the steps
+/// that aren't exact in binary don't print like 4.8000002.
+fn tick_label(i: i64, step: f32) -> String {
+    let decimals = (0..6)
+        .find(|decimals| {
+            let scaled = step * 10_f32.powi(*decimals);
+            (scaled - scaled.round()).abs() < scaled * 1e-4
+        })
+        .unwrap_or(6) as usize;
+
+    format!("{:.*}", decimals, i as f32 * step)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::{CommandsState, RenderCommandType, Vec2f};
+    use crate::rulers::tick_label;
+    use crate::test_utils::TestWorld;
+
+    #[test]
+    fn tick_labels() {
+        assert_eq!("448", tick_label(7, 64.0));
+        assert_eq!("4.8", tick_label(3, 1.6));
+        assert_eq!("-0.96", tick_label(-3, 0.32));
+        assert_eq!("0", tick_label(0, 1.6 * 5.0));
+    }
+
+    #[test]
+    fn rulers() {
+        let mut world = TestWorld::with_view_port(640, 480);
+
+        crate::push_on_touch_move_request_command(world.state(), 0, 0, Vec2f::new(200.0, 100.0));
+
+        // The camera registry has the view port since the first step.
+        world.step();
+        world.render(|state| {
+            let commands_state = state.world.resources.get::<CommandsState>().unwrap();
+            let commands = &commands_state.render_commands;
+
+            let labels = commands
+                .iter()
+                .filter(|command| command.command_type == RenderCommandType::PushString)
+                .map(|command| command.data.string.data_to_string().unwrap())
+                .collect::<Vec<String>>();
+
+            // The world origin is at the top left corner, under the rulers.
+            assert!(!labels.contains(&String::from("0")));
+            assert!(labels.contains(&String::from("64")));
+            assert!(labels.contains(&String::from("448")));
+
+            let marker = commands.iter().any(|command| {
+                command.command_type == RenderCommandType::PushVec2f
+                    && command.data.vec2f == Vec2f::new(200.0, 0.0)
+            });
+
+            assert!(marker);
+
+            // The minor ticks skip the major ones.
+            let major_tick_ends = commands
+                .iter()
+                .filter(|command| {
+                    command.command_type == RenderCommandType::PushVec2f
+                        && command.data.vec2f == Vec2f::new(64.0, 20.0)
+                })
+                .count();
+
+            assert_eq!(1, major_tick_ends);
+        });
+    }
+}
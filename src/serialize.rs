@@ -1,274 +1,842 @@
 extern crate flatbuffers;
 
-// use std::slice;
-
-use crate::commands::{ExecutionCommand, RenderCommand, RequestCommand};
+use std::alloc::Layout;
+use std::error::Error;
+use std::ptr;
+use std::slice;
+use std::str;
+use std::str::Utf8Error;
+
+use serde::{Deserialize, Serialize};
+
+use crate::commands::{
+    Color, CommandData, ExecutionCommand, RenderCommand, RequestCommand, RequestCommandType, Vec2f,
+    Vec2i,
+};
+use crate::commands_capnp;
+use crate::flatbuffers_commands::tech_paws::schemes;
 use crate::memory::MemoryState;
 use crate::RawBuffer;
 
-// use crate::flatbuffers_commands::tech_paws::schemes;
+pub type SerializeResult<T> = Result<T, Box<dyn Error>>;
 
 pub fn serialize_json_render_commands(
-    _memory: &mut MemoryState,
-    _commands: &[RenderCommand],
-) -> RawBuffer {
-    todo!();
-    // let json = serde_json::to_vec(commands).expect("failed to serialize render commands");
-    // let data = memory.serialize_buffer.alloc_slice_copy(json.as_slice());
-
-    // RawBuffer {
-    //     data: data.as_ptr(),
-    //     length: json.len(),
-    // }
+    memory: &mut MemoryState,
+    commands: &[RenderCommand],
+) -> SerializeResult<RawBuffer> {
+    let json = serde_json::to_vec(commands)?;
+
+    Ok(copy_to_serialize_buffer(memory, &json))
 }
 
 pub fn serialize_json_exec_commands(
-    _memory: &mut MemoryState,
-    _commands: &[ExecutionCommand],
-) -> RawBuffer {
-    todo!();
-    // let json = serde_json::to_vec(commands).expect("failed to serialize execution commands");
-    // let data = memory.serialize_buffer.alloc_slice_copy(json.as_slice());
-
-    // RawBuffer {
-    //     data: data.as_ptr(),
-    //     length: data.len(),
-    // }
+    memory: &mut MemoryState,
+    commands: &[ExecutionCommand],
+) -> SerializeResult<RawBuffer> {
+    let json = serde_json::to_vec(commands)?;
+
+    Ok(copy_to_serialize_buffer(memory, &json))
 }
 
+/// String payloads are copied to the serialize buffer,
+/// so they stay valid until the next flush.
 pub fn deserialize_json_request_commands(
-    _data: RawBuffer,
-) -> serde_json::Result<Vec<RequestCommand>> {
-    todo!();
-    // let bytes = unsafe { slice::from_raw_parts(data.data, data.length) };
-    // serde_json::from_slice::<Vec<RequestCommand>>(bytes)
+    memory: &mut MemoryState,
+    data: RawBuffer,
+) -> SerializeResult<Vec<RequestCommand>> {
+    let bytes = unsafe { slice::from_raw_parts(data.data, data.length) };
+    let commands = serde_json::from_slice::<Vec<JsonCommand<RequestCommandType>>>(bytes)?;
+
+    Ok(commands
+        .into_iter()
+        .map(|command| RequestCommand {
+            command_type: command.command_type,
+            data: create_command_data_from_json(memory, command.data),
+        })
+        .collect())
+}
+
+/// Owned command in the JSON format, unlike `CommandData`
+/// it doesn't reference the serialize buffer.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JsonCommand<T> {
+    pub command_type: T,
+    #[serde(default)]
+    pub data: JsonCommandData,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct JsonCommandData {
+    pub int32: i32,
+    pub vec2f: Vec2f,
+    pub vec2i: Vec2i,
+    pub color: Color,
+    pub string: String,
+}
+
+impl<T> JsonCommand<T> {
+    /// Fails on invalid UTF-8 strings the same way as serializing `RawBuffer`.
+    pub fn new(command_type: T, data: &CommandData) -> Result<JsonCommand<T>, Utf8Error> {
+        Ok(JsonCommand {
+            command_type,
+            data: JsonCommandData {
+                int32: data.int32,
+                vec2f: data.vec2f,
+                vec2i: data.vec2i,
+                color: data.color,
+                string: data.string.data_to_string()?,
+            },
+        })
+    }
+}
+
+fn create_command_data_from_json(memory: &mut MemoryState, data: JsonCommandData) -> CommandData {
+    let string = memory.serialize_buffer.alloc_str(&data.string);
+
+    CommandData {
+        int32: data.int32,
+        vec2f: data.vec2f,
+        vec2i: data.vec2i,
+        color: data.color,
+        string: RawBuffer::from_string(string),
+    }
+}
+
+pub fn serialize_flatbuffers_render_commands(
+    memory: &mut MemoryState,
+    commands: &[RenderCommand],
+) -> SerializeResult<RawBuffer> {
+    let mut builder = flatbuffers::FlatBufferBuilder::with_capacity(1024);
+    let mut commands_schemes = Vec::with_capacity(commands.len());
+
+    for command in commands {
+        let data = create_scheme_command_data(&mut builder, &command.data)?;
+        let command = schemes::RenderCommand::create(
+            &mut builder,
+            &schemes::RenderCommandArgs {
+                type_: schemes::RenderCommandType(command.command_type as i32),
+                data: Some(data),
+            },
+        );
+
+        commands_schemes.push(command);
+    }
+
+    let commands = builder.create_vector(&commands_schemes);
+    let root = schemes::RenderCommands::create(
+        &mut builder,
+        &schemes::RenderCommandsArgs {
+            commands: Some(commands),
+        },
+    );
+
+    builder.finish(root, None);
+
+    Ok(copy_to_serialize_buffer(memory, builder.finished_data()))
+}
+
+pub fn serialize_flatbuffers_exec_commands(
+    memory: &mut MemoryState,
+    commands: &[ExecutionCommand],
+) -> SerializeResult<RawBuffer> {
+    let mut builder = flatbuffers::FlatBufferBuilder::with_capacity(1024);
+    let mut commands_schemes = Vec::with_capacity(commands.len());
+
+    for command in commands {
+        let data = create_scheme_command_data(&mut builder, &command.data)?;
+        let command = schemes::ExecutionCommand::create(
+            &mut builder,
+            &schemes::ExecutionCommandArgs {
+                type_: schemes::ExecutionCommandType(command.command_type as i32),
+                data: Some(data),
+            },
+        );
+
+        commands_schemes.push(command);
+    }
+
+    let commands = builder.create_vector(&commands_schemes);
+    let root = schemes::ExecutionCommands::create(
+        &mut builder,
+        &schemes::ExecutionCommandsArgs {
+            commands: Some(commands),
+        },
+    );
+
+    builder.finish(root, None);
+
+    Ok(copy_to_serialize_buffer(memory, builder.finished_data()))
+}
+
+/// The buffer is verified before reading, string payloads are copied
+/// to the serialize buffer, so they stay valid until the next flush.
+pub fn deserialize_flatbuffers_request_commands(
+    memory: &mut MemoryState,
+    data: RawBuffer,
+) -> SerializeResult<Vec<RequestCommand>> {
+    let bytes = unsafe { slice::from_raw_parts(data.data, data.length) };
+    let root = flatbuffers::root::<schemes::RequestCommands>(bytes)?;
+
+    let commands = match root.commands() {
+        Some(commands) => commands,
+        None => return Ok(Vec::new()),
+    };
+
+    let mut requests = Vec::with_capacity(commands.len());
+
+    for command in commands {
+        requests.push(RequestCommand {
+            command_type: create_request_command_type_from_flatbuffers(command.type_())?,
+            data: create_command_data_from_flatbuffers(memory, command.data()),
+        });
+    }
+
+    Ok(requests)
+}
+
+/// The copy is aligned to 8 bytes, so the host can read messages in place.
+fn copy_to_serialize_buffer(memory: &mut MemoryState, data: &[u8]) -> RawBuffer {
+    let layout = Layout::from_size_align(data.len(), 8).expect("invalid serialize buffer layout");
+    let buffer = memory.serialize_buffer.alloc_layout(layout).as_ptr();
+
+    unsafe {
+        ptr::copy_nonoverlapping(data.as_ptr(), buffer, data.len());
+        RawBuffer::from_bytes(slice::from_raw_parts(buffer, data.len()))
+    }
+}
+
+/// Default values are omitted, the reader gets them back from the schema.
+fn create_scheme_command_data<'a>(
+    builder: &mut flatbuffers::FlatBufferBuilder<'a>,
+    data: &CommandData,
+) -> SerializeResult<flatbuffers::WIPOffset<schemes::CommandData<'a>>> {
+    let string = if data.string.length > 0 {
+        let bytes = unsafe { slice::from_raw_parts(data.string.data, data.string.length) };
+        Some(builder.create_string(str::from_utf8(bytes)?))
+    }
+    else {
+        None
+    };
+
+    let vec2f = schemes::Vec2f::new(data.vec2f.x, data.vec2f.y);
+    let vec2i = schemes::Vec2i::new(data.vec2i.x, data.vec2i.y);
+    let color = schemes::Color::new(data.color.r, data.color.g, data.color.b, data.color.a);
+
+    Ok(schemes::CommandData::create(
+        builder,
+        &schemes::CommandDataArgs {
+            int32: data.int32,
+            vec2f: if data.vec2f != Vec2f::default() {
+                Some(&vec2f)
+            }
+            else {
+                None
+            },
+            vec2i: if data.vec2i != Vec2i::default() {
+                Some(&vec2i)
+            }
+            else {
+                None
+            },
+            color: if data.color != Color::default() {
+                Some(&color)
+            }
+            else {
+                None
+            },
+            string,
+        },
+    ))
+}
+
+fn create_command_data_from_flatbuffers(
+    memory: &mut MemoryState,
+    data: Option<schemes::CommandData>,
+) -> CommandData {
+    let data = match data {
+        Some(data) => data,
+        None => return CommandData::default(),
+    };
+
+    let string = memory
+        .serialize_buffer
+        .alloc_str(data.string().unwrap_or(""));
+
+    CommandData {
+        int32: data.int32(),
+        vec2f: data
+            .vec2f()
+            .map(|vec2f| Vec2f::new(vec2f.x(), vec2f.y()))
+            .unwrap_or_default(),
+        vec2i: data
+            .vec2i()
+            .map(|vec2i| Vec2i::new(vec2i.x(), vec2i.y()))
+            .unwrap_or_default(),
+        color: data
+            .color()
+            .map(|color| Color::rgba(color.r(), color.g(), color.b(), color.a()))
+            .unwrap_or_default(),
+        string: RawBuffer::from_string(string),
+    }
+}
+
+fn create_request_command_type_from_flatbuffers(
+    command_type: schemes::RequestCommandType,
+) -> SerializeResult<RequestCommandType> {
+    let command_type = match command_type {
+        schemes::RequestCommandType::PushVec2f => RequestCommandType::PushVec2f,
+        schemes::RequestCommandType::SetViewportSize => RequestCommandType::SetViewportSize,
+        schemes::RequestCommandType::OnTouchStart => RequestCommandType::OnTouchStart,
+        schemes::RequestCommandType::OnTouchEnd => RequestCommandType::OnTouchEnd,
+        schemes::RequestCommandType::OnTouchMove => RequestCommandType::OnTouchMove,
+        schemes::RequestCommandType::PushVec2i => RequestCommandType::PushVec2i,
+        schemes::RequestCommandType::PushInt32 => RequestCommandType::PushInt32,
+        schemes::RequestCommandType::OnKeyDown => RequestCommandType::OnKeyDown,
+        schemes::RequestCommandType::OnKeyUp => RequestCommandType::OnKeyUp,
+        schemes::RequestCommandType::OnTextInput => RequestCommandType::OnTextInput,
+        schemes::RequestCommandType::OnScroll => RequestCommandType::OnScroll,
+        schemes::RequestCommandType::CameraFit => RequestCommandType::CameraFit,
+        schemes::RequestCommandType::CameraGoTo => RequestCommandType::CameraGoTo,
+        schemes::RequestCommandType::CameraSaveBookmark => RequestCommandType::CameraSaveBookmark,
+        schemes::RequestCommandType::CameraLoadBookmark => RequestCommandType::CameraLoadBookmark,
+        _ => return Err(format!("unknown request command type {}", command_type.0).into()),
+    };
+
+    Ok(command_type)
+}
+
+pub fn serialize_capnp_render_commands(
+    memory: &mut MemoryState,
+    commands: &[RenderCommand],
+) -> SerializeResult<RawBuffer> {
+    let mut message = capnp::message::Builder::new_default();
+    let root = message.init_root::<commands_capnp::render_commands::Builder>();
+    let mut commands_capnp = root.init_commands(commands.len() as u32);
+
+    for (i, command) in commands.iter().enumerate() {
+        let mut builder = commands_capnp.reborrow().get(i as u32);
+        let command_type = capnp::traits::FromU16::from_u16(command.command_type as u16)?;
+
+        builder.set_command_type(command_type);
+        create_capnp_command_data(builder.init_data(), &command.data)?;
+    }
+
+    let data = capnp::serialize::write_message_to_words(&message);
+
+    Ok(copy_to_serialize_buffer(memory, &data))
+}
+
+pub fn serialize_capnp_exec_commands(
+    memory: &mut MemoryState,
+    commands: &[ExecutionCommand],
+) -> SerializeResult<RawBuffer> {
+    let mut message = capnp::message::Builder::new_default();
+    let root = message.init_root::<commands_capnp::execution_commands::Builder>();
+    let mut commands_capnp = root.init_commands(commands.len() as u32);
+
+    for (i, command) in commands.iter().enumerate() {
+        let mut builder = commands_capnp.reborrow().get(i as u32);
+        let command_type = capnp::traits::FromU16::from_u16(command.command_type as u16)?;
+
+        builder.set_command_type(command_type);
+        create_capnp_command_data(builder.init_data(), &command.data)?;
+    }
+
+    let data = capnp::serialize::write_message_to_words(&message);
+
+    Ok(copy_to_serialize_buffer(memory, &data))
+}
+
+/// Expects the standard stream framing, string payloads are copied
+/// to the serialize buffer, so they stay valid until the next flush.
+pub fn deserialize_capnp_request_commands(
+    memory: &mut MemoryState,
+    data: RawBuffer,
+) -> SerializeResult<Vec<RequestCommand>> {
+    let bytes = unsafe { slice::from_raw_parts(data.data, data.length) };
+    // NOTE: Copies the segments, the host buffer isn't guaranteed to be word aligned.
+    let message = capnp::serialize::read_message(bytes, capnp::message::ReaderOptions::new())?;
+    let root = message.get_root::<commands_capnp::request_commands::Reader>()?;
+    let commands = root.get_commands()?;
+
+    let mut requests = Vec::with_capacity(commands.len() as usize);
+
+    for command in commands.iter() {
+        requests.push(RequestCommand {
+            command_type: create_request_command_type_from_capnp(command.get_command_type()?),
+            data: create_command_data_from_capnp(memory, command.get_data()?)?,
+        });
+    }
+
+    Ok(requests)
+}
+
+/// Default values are left unset, the reader gets them back from the schema.
+fn create_capnp_command_data(
+    mut builder: commands_capnp::command_data::Builder,
+    data: &CommandData,
+) -> SerializeResult<()> {
+    builder.set_int32(data.int32);
+
+    if data.vec2f != Vec2f::default() {
+        let mut vec2f = builder.reborrow().init_vec2f();
+        vec2f.set_x(data.vec2f.x);
+        vec2f.set_y(data.vec2f.y);
+    }
+
+    if data.vec2i != Vec2i::default() {
+        let mut vec2i = builder.reborrow().init_vec2i();
+        vec2i.set_x(data.vec2i.x);
+        vec2i.set_y(data.vec2i.y);
+    }
+
+    if data.color != Color::default() {
+        let mut color = builder.reborrow().init_color();
+        color.set_r(data.color.r);
+        color.set_g(data.color.g);
+        color.set_b(data.color.b);
+        color.set_a(data.color.a);
+    }
+
+    if data.string.length > 0 {
+        let bytes = unsafe { slice::from_raw_parts(data.string.data, data.string.length) };
+        builder.set_string(str::from_utf8(bytes)?);
+    }
+
+    Ok(())
+}
+
+fn create_command_data_from_capnp(
+    memory: &mut MemoryState,
+    data: commands_capnp::command_data::Reader,
+) -> SerializeResult<CommandData> {
+    let vec2f = data.get_vec2f()?;
+    let vec2i = data.get_vec2i()?;
+    let color = data.get_color()?;
+    let string = memory.serialize_buffer.alloc_str(data.get_string()?);
+
+    Ok(CommandData {
+        int32: data.get_int32(),
+        vec2f: Vec2f::new(vec2f.get_x(), vec2f.get_y()),
+        vec2i: Vec2i::new(vec2i.get_x(), vec2i.get_y()),
+        color: Color::rgba(color.get_r(), color.get_g(), color.get_b(), color.get_a()),
+        string: RawBuffer::from_string(string),
+    })
 }
 
-// TODO:
-// pub fn serialize_flatbuffers_render_commands(
-//     memory: &mut Memory,
-//     commands: &[RenderCommand],
-// ) -> RawBuffer {
-//     let mut builder = flatbuffers::FlatBufferBuilder::new_with_capacity(1024);
-//     let commands_schemes: Vec<flatbuffers::WIPOffset<schemes::RenderCommand>> = commands
-//         .iter()
-//         .map(|command| {
-//             let data = create_scheme_render_command_data(&mut builder, command);
-//             schemes::RenderCommand::create(
-//                 &mut builder,
-//                 &schemes::RenderCommandArgs {
-//                     type_: create_scheme_render_command_type(command),
-//                     data: Some(data),
-//                 },
-//             )
-//         })
-//         .collect();
-
-//     let commands_flatbuffers_vec = builder.create_vector(&commands_schemes);
-
-//     schemes::RenderCommands::create(
-//         &mut builder,
-//         &schemes::RenderCommandsArgs {
-//             commands: Some(commands_flatbuffers_vec),
-//         },
-//     );
-
-//     let data = builder.finished_data();
-
-//     let start = memory.serialize_buffer.len();
-//     let end = start + data.len();
-
-//     memory.serialize_buffer.extend(data.iter());
-
-//     let data = memory.serialize_buffer[start..end].as_ptr();
-
-//     RawBuffer {
-//         data,
-//         length: end - start,
-//     }
-// }
-
-// fn create_scheme_render_command_type(command: &RenderCommand) -> schemes::RenderCommandType {
-//     match command {
-//         RenderCommand::PushColor { .. } => schemes::RenderCommandType::PushColor,
-//         RenderCommand::PushPos2f { .. } => schemes::RenderCommandType::PushPos2f,
-//         RenderCommand::PushSize2f { .. } => schemes::RenderCommandType::PushSize2f,
-//         RenderCommand::PushTexture { .. } => schemes::RenderCommandType::PushTexture,
-//         RenderCommand::SetColorUniform => schemes::RenderCommandType::SetColorUniform,
-//         RenderCommand::PushColorShader => schemes::RenderCommandType::PushColorShader,
-//         RenderCommand::PushTextureShader => schemes::RenderCommandType::PushTextureShader,
-//         RenderCommand::DrawLines => schemes::RenderCommandType::DrawLines,
-//         RenderCommand::DrawPoints => schemes::RenderCommandType::DrawPoints,
-//         RenderCommand::DrawQuads => schemes::RenderCommandType::DrawQuads,
-//     }
-// }
-
-// fn create_scheme_render_command_data<'a, 'b>(
-//     mut builder: &'a mut flatbuffers::FlatBufferBuilder<'b>,
-//     command: &'a RenderCommand,
-// ) -> flatbuffers::WIPOffset<schemes::Data<'b>> {
-//     let mut vec2f: Option<&schemes::Vec2f> = None;
-//     let mut color: Option<&schemes::Color> = None;
-
-//     let vec2f_data;
-//     let color_data;
-
-//     match *command {
-//         RenderCommand::PushColor { r, g, b, a } => {
-//             color_data = schemes::Color::new(r, g, b, a);
-//             color = Some(&color_data);
-//         }
-//         RenderCommand::PushPos2f { x, y } => {
-//             vec2f_data = schemes::Vec2f::new(x, y);
-//             vec2f = Some(&vec2f_data);
-//         }
-//         RenderCommand::PushSize2f { x, y } => {
-//             vec2f_data = schemes::Vec2f::new(x, y);
-//             vec2f = Some(&vec2f_data);
-//         }
-//         RenderCommand::PushTexture { .. } => {}
-//         RenderCommand::SetColorUniform => {}
-//         RenderCommand::PushColorShader => {}
-//         RenderCommand::PushTextureShader => {}
-//         RenderCommand::DrawLines => {}
-//         RenderCommand::DrawPoints => {}
-//         RenderCommand::DrawQuads => {}
-//     }
-
-//     schemes::Data::create(
-//         &mut builder,
-//         &schemes::DataArgs {
-//             color,
-//             vec2f,
-//             vec2i: None,
-//         },
-//     )
-// }
-
-// pub fn serialize_flatbuffers_exec_commands(
-//     memory: &mut Memory,
-//     commands: &[ExecutionCommand],
-// ) -> RawBuffer {
-//     let mut builder = flatbuffers::FlatBufferBuilder::new_with_capacity(1024);
-//     let commands_schemes: Vec<flatbuffers::WIPOffset<schemes::ExecutionCommand>> = commands
-//         .iter()
-//         .map(|command| {
-//             let data = create_scheme_execution_command_data(&mut builder, command);
-//             schemes::ExecutionCommand::create(
-//                 &mut builder,
-//                 &schemes::ExecutionCommandArgs {
-//                     type_: create_scheme_execution_command_type(command),
-//                     data: Some(data),
-//                 },
-//             )
-//         })
-//         .collect();
-
-//     let commands_flatbuffers_vec = builder.create_vector(&commands_schemes);
-
-//     schemes::ExecutionCommands::create(
-//         &mut builder,
-//         &schemes::ExecutionCommandsArgs {
-//             commands: Some(commands_flatbuffers_vec),
-//         },
-//     );
-
-//     let data = builder.finished_data();
-
-//     let start = memory.serialize_buffer.len();
-//     let end = start + data.len();
-
-//     memory.serialize_buffer.extend(data.iter());
-
-//     let data = memory.serialize_buffer[start..end].as_ptr();
-
-//     RawBuffer {
-//         data,
-//         length: end - start,
-//     }
-// }
-
-// fn create_scheme_execution_command_type(
-//     command: &ExecutionCommand,
-// ) -> schemes::ExecutionCommandType {
-//     match command {
-//         ExecutionCommand::PushPos2f { .. } => schemes::ExecutionCommandType::PushPos2f,
-//         ExecutionCommand::UpdateCameraPosition => {
-//             schemes::ExecutionCommandType::UpdateCameraPosition
-//         }
-//     }
-// }
-
-// fn create_scheme_execution_command_data<'a, 'b>(
-//     mut builder: &'a mut flatbuffers::FlatBufferBuilder<'b>,
-//     command: &'a ExecutionCommand,
-// ) -> flatbuffers::WIPOffset<schemes::Data<'b>> {
-//     let mut vec2f: Option<&schemes::Vec2f> = None;
-//     let vec2f_data;
-
-//     match *command {
-//         ExecutionCommand::PushPos2f { x, y } => {
-//             vec2f_data = schemes::Vec2f::new(x, y);
-//             vec2f = Some(&vec2f_data);
-//         }
-//         ExecutionCommand::UpdateCameraPosition => {}
-//     }
-
-//     schemes::Data::create(
-//         &mut builder,
-//         &schemes::DataArgs {
-//             vec2f,
-//             vec2i: None,
-//             color: None,
-//         },
-//     )
-// }
-
-// pub fn deserialize_flatbuffers_request_commands(data: RawBuffer) -> Vec<RequestCommand> {
-//     let bytes = unsafe { slice::from_raw_parts(data.data, data.length) };
-//     let flatbuffer_commands = flatbuffers::get_root::<schemes::RequestCommands>(bytes).commands();
-
-//     match flatbuffer_commands {
-//         Some(commands) => {
-//             let mut vec = Vec::with_capacity(commands.len());
-
-//             for command in commands {
-//                 let command = create_request_command_from_flatbuffers(command);
-
-//                 if let Some(command) = command {
-//                     vec.push(command);
-//                 }
-//             }
-
-//             vec
-//         }
-//         None => vec![],
-//     }
-// }
-
-// fn create_request_command_from_flatbuffers(
-//     command: schemes::RequestCommand,
-// ) -> Option<RequestCommand> {
-//     let data = command.data()?;
-
-//     let command = match command.type_() {
-//         schemes::RequestCommandType::SetViewportSize => RequestCommand::SetViewportSize {
-//             width: data.vec2i()?.x(),
-//             height: data.vec2i()?.y(),
-//         },
-//         schemes::RequestCommandType::OnTouchStart => RequestCommand::OnTouchStart {
-//             x: data.vec2f()?.x(),
-//             y: data.vec2f()?.y(),
-//         },
-//         schemes::RequestCommandType::OnTouchEnd => RequestCommand::OnTouchEnd {
-//             x: data.vec2f()?.x(),
-//             y: data.vec2f()?.y(),
-//         },
-//         schemes::RequestCommandType::OnTouchMove => RequestCommand::OnTouchMove {
-//             x: data.vec2f()?.x(),
-//             y: data.vec2f()?.y(),
-//         },
-//     };
-
-//     Some(command)
-// }
+fn create_request_command_type_from_capnp(
+    command_type: commands_capnp::RequestCommandType,
+) -> RequestCommandType {
+    match command_type {
+        commands_capnp::RequestCommandType::PushVec2f => RequestCommandType::PushVec2f,
+        commands_capnp::RequestCommandType::SetViewportSize => RequestCommandType::SetViewportSize,
+        commands_capnp::RequestCommandType::OnTouchStart => RequestCommandType::OnTouchStart,
+        commands_capnp::RequestCommandType::OnTouchEnd => RequestCommandType::OnTouchEnd,
+        commands_capnp::RequestCommandType::OnTouchMove => RequestCommandType::OnTouchMove,
+        commands_capnp::RequestCommandType::PushVec2i => RequestCommandType::PushVec2i,
+        commands_capnp::RequestCommandType::PushInt32 => RequestCommandType::PushInt32,
+        commands_capnp::RequestCommandType::OnKeyDown => RequestCommandType::OnKeyDown,
+        commands_capnp::RequestCommandType::OnKeyUp => RequestCommandType::OnKeyUp,
+        commands_capnp::RequestCommandType::OnTextInput => RequestCommandType::OnTextInput,
+        commands_capnp::RequestCommandType::OnScroll => RequestCommandType::OnScroll,
+        commands_capnp::RequestCommandType::CameraFit => RequestCommandType::CameraFit,
+        commands_capnp::RequestCommandType::CameraGoTo => RequestCommandType::CameraGoTo,
+        commands_capnp::RequestCommandType::CameraSaveBookmark => {
+            RequestCommandType::CameraSaveBookmark
+        }
+        commands_capnp::RequestCommandType::CameraLoadBookmark => {
+            RequestCommandType::CameraLoadBookmark
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::slice;
+
+    use crate::commands::{
+        Color, CommandData, ExecutionCommand, ExecutionCommandType, RenderCommand,
+        RenderCommandType, RequestCommandType, Vec2f, Vec2i,
+    };
+    use crate::commands_capnp;
+    use crate::components::ViewPortSize;
+    use crate::errors::StatusCode;
+    use crate::memory::MemoryState;
+    use crate::serialize::*;
+    use crate::test_utils::TestWorld;
+    use crate::RawBuffer;
+
+    fn create_command_data(value: i32) -> CommandData {
+        let value_f32 = value as f32;
+
+        CommandData {
+            int32: value,
+            vec2f: Vec2f::new(value_f32, -value_f32),
+            vec2i: Vec2i::new(-value, value),
+            color: Color::rgba(value_f32, 0.25, 0.5, 1.0),
+            string: RawBuffer::from_string("text"),
+        }
+    }
+
+    fn assert_capnp_command_data(
+        expected: &CommandData,
+        data: commands_capnp::command_data::Reader,
+    ) {
+        let vec2f = data.get_vec2f().unwrap();
+        let vec2i = data.get_vec2i().unwrap();
+        let color = data.get_color().unwrap();
+
+        assert_eq!(expected.int32, data.get_int32());
+        assert_eq!(expected.vec2f, Vec2f::new(vec2f.get_x(), vec2f.get_y()));
+        assert_eq!(expected.vec2i, Vec2i::new(vec2i.get_x(), vec2i.get_y()));
+        assert_eq!(
+            expected.color,
+            Color::rgba(color.get_r(), color.get_g(), color.get_b(), color.get_a())
+        );
+        assert_eq!(
+            expected.string.data_to_string().unwrap(),
+            data.get_string().unwrap()
+        );
+    }
+
+    fn read_capnp_message(
+        data: RawBuffer,
+    ) -> capnp::message::Reader<capnp::serialize::OwnedSegments> {
+        let bytes = unsafe { slice::from_raw_parts(data.data, data.length) };
+        capnp::serialize::read_message(bytes, capnp::message::ReaderOptions::new()).unwrap()
+    }
+
+    #[test]
+    fn capnp_render_commands() {
+        let mut memory = MemoryState::default();
+        let command_types = [
+            RenderCommandType::PushColor,
+            RenderCommandType::PushVec2f,
+            RenderCommandType::SetColorUniform,
+            RenderCommandType::PushColorShader,
+            RenderCommandType::DrawLines,
+            RenderCommandType::DrawPoints,
+            RenderCommandType::DrawQuads,
+            RenderCommandType::DrawText,
+            RenderCommandType::PushString,
+            RenderCommandType::PushTextShader,
+            RenderCommandType::SetCamera,
+            RenderCommandType::PushInt32,
+        ];
+        let commands: Vec<RenderCommand> = command_types
+            .iter()
+            .map(|command_type| RenderCommand {
+                command_type: *command_type,
+                data: create_command_data(*command_type as i32),
+            })
+            .collect();
+
+        let data = serialize_capnp_render_commands(&mut memory, &commands).unwrap();
+        let message = read_capnp_message(data);
+        let root = message
+            .get_root::<commands_capnp::render_commands::Reader>()
+            .unwrap();
+        let commands_capnp = root.get_commands().unwrap();
+
+        assert_eq!(commands.len(), commands_capnp.len() as usize);
+
+        for (command, command_capnp) in commands.iter().zip(commands_capnp.iter()) {
+            let command_type = command_capnp.get_command_type().unwrap();
+
+            assert_eq!(command.command_type as u16, command_type as u16);
+            assert_capnp_command_data(&command.data, command_capnp.get_data().unwrap());
+        }
+    }
+
+    #[test]
+    fn capnp_exec_commands() {
+        let mut memory = MemoryState::default();
+        let command_types = [
+            ExecutionCommandType::PushVec2f,
+            ExecutionCommandType::UpdateCameraPosition,
+            ExecutionCommandType::PushInt32,
+            ExecutionCommandType::SetCursor,
+            ExecutionCommandType::UpdateCameraTransform,
+            ExecutionCommandType::UpdateCameraViewPort,
+            ExecutionCommandType::RemoveCamera,
+        ];
+        let commands: Vec<ExecutionCommand> = command_types
+            .iter()
+            .map(|command_type| ExecutionCommand {
+                command_type: *command_type,
+                data: create_command_data(*command_type as i32),
+            })
+            .collect();
+
+        let data = serialize_capnp_exec_commands(&mut memory, &commands).unwrap();
+        let message = read_capnp_message(data);
+        let root = message
+            .get_root::<commands_capnp::execution_commands::Reader>()
+            .unwrap();
+        let commands_capnp = root.get_commands().unwrap();
+
+        assert_eq!(commands.len(), commands_capnp.len() as usize);
+
+        for (command, command_capnp) in commands.iter().zip(commands_capnp.iter()) {
+            let command_type = command_capnp.get_command_type().unwrap();
+
+            assert_eq!(command.command_type as u16, command_type as u16);
+            assert_capnp_command_data(&command.data, command_capnp.get_data().unwrap());
+        }
+    }
+
+    #[test]
+    fn capnp_request_commands() {
+        let mut memory = MemoryState::default();
+        let command_types = [
+            commands_capnp::RequestCommandType::PushVec2f,
+            commands_capnp::RequestCommandType::SetViewportSize,
+            commands_capnp::RequestCommandType::OnTouchStart,
+            commands_capnp::RequestCommandType::OnTouchEnd,
+            commands_capnp::RequestCommandType::OnTouchMove,
+            commands_capnp::RequestCommandType::PushVec2i,
+            commands_capnp::RequestCommandType::PushInt32,
+            commands_capnp::RequestCommandType::OnKeyDown,
+            commands_capnp::RequestCommandType::OnKeyUp,
+            commands_capnp::RequestCommandType::OnTextInput,
+            commands_capnp::RequestCommandType::OnScroll,
+            commands_capnp::RequestCommandType::CameraFit,
+            commands_capnp::RequestCommandType::CameraGoTo,
+            commands_capnp::RequestCommandType::CameraSaveBookmark,
+            commands_capnp::RequestCommandType::CameraLoadBookmark,
+        ];
+        let expected_command_types = [
+            RequestCommandType::PushVec2f,
+            RequestCommandType::SetViewportSize,
+            RequestCommandType::OnTouchStart,
+            RequestCommandType::OnTouchEnd,
+            RequestCommandType::OnTouchMove,
+            RequestCommandType::PushVec2i,
+            RequestCommandType::PushInt32,
+            RequestCommandType::OnKeyDown,
+            RequestCommandType::OnKeyUp,
+            RequestCommandType::OnTextInput,
+            RequestCommandType::OnScroll,
+            RequestCommandType::CameraFit,
+            RequestCommandType::CameraGoTo,
+            RequestCommandType::CameraSaveBookmark,
+            RequestCommandType::CameraLoadBookmark,
+        ];
+
+        let mut message = capnp::message::Builder::new_default();
+        let root = message.init_root::<commands_capnp::request_commands::Builder>();
+        let mut commands_capnp = root.init_commands(command_types.len() as u32);
+
+        for (i, command_type) in command_types.iter().enumerate() {
+            let mut command = commands_capnp.reborrow().get(i as u32);
+            command.set_command_type(*command_type);
+            create_capnp_command_data(command.init_data(), &create_command_data(i as i32)).unwrap();
+        }
+
+        let bytes = capnp::serialize::write_message_to_words(&message);
+        let commands =
+            deserialize_capnp_request_commands(&mut memory, RawBuffer::from_bytes(&bytes)).unwrap();
+
+        assert_eq!(expected_command_types.len(), commands.len());
+
+        for (i, command) in commands.iter().enumerate() {
+            let expected = create_command_data(i as i32);
+
+            assert_eq!(
+                expected_command_types[i] as u16,
+                command.command_type as u16
+            );
+            assert_eq!(expected.int32, command.data.int32);
+            assert_eq!(expected.vec2f, command.data.vec2f);
+            assert_eq!(expected.vec2i, command.data.vec2i);
+            assert_eq!(expected.color, command.data.color);
+            assert_eq!(
+                expected.string.data_to_string().unwrap(),
+                command.data.string.data_to_string().unwrap()
+            );
+        }
+
+        let invalid = [0xff; 16];
+        let result =
+            deserialize_capnp_request_commands(&mut memory, RawBuffer::from_bytes(&invalid));
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn json_command_strings() {
+        let command = JsonCommand::new(RenderCommandType::PushString, &CommandData::string("text"));
+        assert_eq!("text", command.unwrap().data.string);
+
+        let invalid = CommandData::string_bytes(&[0xff, 0xfe]);
+        assert!(JsonCommand::new(RenderCommandType::PushString, &invalid).is_err());
+    }
+
+    #[test]
+    fn json_commands() {
+        let mut world = TestWorld::new();
+        let requests = r#"[
+            {"command_type": "PushVec2i", "data": {"vec2i": {"x": 100, "y": 200}}},
+            {"command_type": "SetViewportSize"}
+        ]"#;
+
+        let status = unsafe {
+            crate::send_request_commands(
+                world.handle,
+                crate::SerializeFormat::Json,
+                RawBuffer::from_string(requests),
+            )
+        };
+
+        assert_eq!(StatusCode::Ok, status);
+        assert_eq!(StatusCode::Ok, unsafe { crate::step(world.handle) });
+        assert_eq!(StatusCode::Ok, unsafe { crate::render_pass2(world.handle) });
+
+        {
+            let state = world.state();
+            let view_port = state.world.resources.get::<ViewPortSize>().unwrap();
+
+            assert_eq!(100, view_port.width);
+            assert_eq!(200, view_port.height);
+        }
+
+        let mut data = RawBuffer::default();
+        let status = unsafe {
+            crate::get_render_commands(world.handle, crate::SerializeFormat::Json, &mut data)
+        };
+
+        assert_eq!(StatusCode::Ok, status);
+
+        let json: serde_json::Value =
+            serde_json::from_str(&data.data_to_string().unwrap()).unwrap();
+        let commands = json.as_array().unwrap();
+
+        assert!(!commands.is_empty());
+        assert_eq!("SetCamera", commands[1]["command_type"]);
+        assert_eq!(1, commands[0]["data"]["int32"]);
+
+        let mut data = RawBuffer::default();
+        let status = unsafe {
+            crate::get_exec_commands_ser(world.handle, crate::SerializeFormat::Json, &mut data)
+        };
+
+        assert_eq!(StatusCode::Ok, status);
+
+        let status = unsafe {
+            crate::send_request_commands(
+                world.handle,
+                crate::SerializeFormat::Json,
+                RawBuffer::from_string("[{\"command_type\": \"Unknown\"}]"),
+            )
+        };
+
+        assert_eq!(StatusCode::InvalidArgument, status);
+    }
+
+    #[test]
+    fn flatbuffers_commands() {
+        use crate::flatbuffers_commands::tech_paws::schemes;
+
+        let mut world = TestWorld::new();
+        let mut builder = flatbuffers::FlatBufferBuilder::new();
+        let view_port_size = schemes::Vec2i::new(100, 200);
+        let data = schemes::CommandData::create(
+            &mut builder,
+            &schemes::CommandDataArgs {
+                vec2i: Some(&view_port_size),
+                ..Default::default()
+            },
+        );
+        let push_vec2i = schemes::RequestCommand::create(
+            &mut builder,
+            &schemes::RequestCommandArgs {
+                type_: schemes::RequestCommandType::PushVec2i,
+                data: Some(data),
+            },
+        );
+        let set_view_port_size = schemes::RequestCommand::create(
+            &mut builder,
+            &schemes::RequestCommandArgs {
+                type_: schemes::RequestCommandType::SetViewportSize,
+                data: None,
+            },
+        );
+        let commands = builder.create_vector(&[push_vec2i, set_view_port_size]);
+        let root = schemes::RequestCommands::create(
+            &mut builder,
+            &schemes::RequestCommandsArgs {
+                commands: Some(commands),
+            },
+        );
+        builder.finish(root, None);
+
+        let status = unsafe {
+            crate::send_request_commands(
+                world.handle,
+                crate::SerializeFormat::FlatBuffers,
+                RawBuffer::from_bytes(builder.finished_data()),
+            )
+        };
+
+        assert_eq!(StatusCode::Ok, status);
+        assert_eq!(StatusCode::Ok, unsafe { crate::step(world.handle) });
+        assert_eq!(StatusCode::Ok, unsafe { crate::render_pass2(world.handle) });
+
+        {
+            let state = world.state();
+            let view_port = state.world.resources.get::<ViewPortSize>().unwrap();
+
+            assert_eq!(100, view_port.width);
+            assert_eq!(200, view_port.height);
+        }
+
+        let mut data = RawBuffer::default();
+        let status = unsafe {
+            crate::get_render_commands(world.handle, crate::SerializeFormat::FlatBuffers, &mut data)
+        };
+
+        assert_eq!(StatusCode::Ok, status);
+
+        let bytes = unsafe { slice::from_raw_parts(data.data, data.length) };
+        let commands = flatbuffers::root::<schemes::RenderCommands>(bytes)
+            .unwrap()
+            .commands()
+            .unwrap();
+
+        assert!(!commands.is_empty());
+        assert_eq!(
+            schemes::RenderCommandType::PushInt32,
+            commands.get(0).type_()
+        );
+        assert_eq!(1, commands.get(0).data().unwrap().int32());
+        assert_eq!(
+            schemes::RenderCommandType::SetCamera,
+            commands.get(1).type_()
+        );
+
+        let mut data = RawBuffer::default();
+        let status = unsafe {
+            crate::get_exec_commands_ser(
+                world.handle,
+                crate::SerializeFormat::FlatBuffers,
+                &mut data,
+            )
+        };
+
+        assert_eq!(StatusCode::Ok, status);
+
+        let bytes = unsafe { slice::from_raw_parts(data.data, data.length) };
+        assert!(flatbuffers::root::<schemes::ExecutionCommands>(bytes).is_ok());
+
+        let status = unsafe {
+            crate::send_request_commands(
+                world.handle,
+                crate::SerializeFormat::FlatBuffers,
+                RawBuffer::from_bytes(&[0xff, 0xff, 0xff, 0xff, 0x00]),
+            )
+        };
+
+        assert_eq!(StatusCode::InvalidArgument, status);
+    }
+}
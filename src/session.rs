@@ -0,0 +1,408 @@
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::ptr;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::commands::*;
+use crate::components::FrameClock;
+use crate::errors::{Error, StatusCode};
+use crate::serialize::JsonCommand;
+use crate::{ApplicationState, RawBuffer, SerializeFormat};
+
+/// Everything the host fed into a world, in call order.
+#[derive(Debug, Default)]
+pub struct Session {
+    events: Vec<SessionEvent>,
+}
+
+// NOTE: Render passes, frame end and text sizes aren't frame boundaries,
+// but the replay isn't deterministic without them. Profiler snapshots are
+// still measured during the replay, so sessions that open the profiler
+// views can't be replayed with `assert_streams`.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "event")]
+pub(crate) enum SessionEvent {
+    RequestCommands {
+        commands: Vec<JsonCommand<RequestCommandType>>,
+    },
+    TextSize {
+        size: Vec2f,
+    },
+    RenderStateFlush,
+    /// Keeps the frame clock, gestures depend on it.
+    FrameStart {
+        #[serde(default)]
+        time: Duration,
+    },
+    Step,
+    RenderPass1,
+    RenderPass2,
+    /// Keeps the measured frame time, the debug overlay renders it.
+    FrameEnd {
+        frame_elapsed: Duration,
+    },
+    /// Keeps the streams produced since the previous flush.
+    Flush {
+        render_commands: Vec<JsonCommand<RenderCommandType>>,
+        exec_commands: Vec<JsonCommand<ExecutionCommandType>>,
+    },
+}
+
+impl SessionEvent {
+    pub(crate) fn request_commands(commands: &[RequestCommand]) -> Result<SessionEvent, Error> {
+        Ok(SessionEvent::RequestCommands {
+            commands: commands
+                .iter()
+                .map(|command| to_json(command.command_type, &command.data))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    pub(crate) fn flush(commands_state: &CommandsState) -> Result<SessionEvent, Error> {
+        Ok(SessionEvent::Flush {
+            render_commands: render_commands_to_json(&commands_state.render_commands)?,
+            exec_commands: exec_commands_to_json(&commands_state.exec_commands)?,
+        })
+    }
+}
+
+impl Session {
+    /// Reads a session written by `SessionRecorder`, one event per line.
+    pub fn load(path: &Path) -> Result<Session, Error> {
+        let file = File::open(path).map_err(|err| Error::io_failed(&err.to_string()))?;
+        let mut events = Vec::new();
+
+        for (index, line) in BufReader::new(file).lines().enumerate() {
+            let line = line.map_err(|err| Error::io_failed(&err.to_string()))?;
+
+            if line.is_empty() {
+                continue;
+            }
+
+            let event = serde_json::from_str(&line).map_err(|err| {
+                Error::invalid_argument(&format!(
+                    "invalid session file at line {}: {}",
+                    index + 1,
+                    err
+                ))
+            })?;
+
+            events.push(event);
+        }
+
+        Ok(Session { events })
+    }
+}
+
+/// Writes the events to the file as they happen, one JSON object per line,
+/// the file is flushed on every `Flush` event so a crashed host keeps
+/// the session up to the last flushed frame.
+pub struct SessionRecorder {
+    writer: BufWriter<File>,
+    /// The first failed write, the events after it are dropped.
+    error: Option<Error>,
+}
+
+impl SessionRecorder {
+    pub fn new(path: &Path) -> Result<SessionRecorder, Error> {
+        let file = File::create(path).map_err(|err| Error::io_failed(&err.to_string()))?;
+
+        Ok(SessionRecorder {
+            writer: BufWriter::new(file),
+            error: None,
+        })
+    }
+
+    pub(crate) fn record(&mut self, event: SessionEvent) {
+        self.try_record(Ok(event));
+    }
+
+    /// A failed event fails the recording the same way as a failed write.
+    pub(crate) fn try_record(&mut self, event: Result<SessionEvent, Error>) {
+        if self.error.is_some() {
+            return;
+        }
+
+        if let Err(err) = event.and_then(|event| self.write_event(&event)) {
+            self.error = Some(err);
+        }
+    }
+
+    fn write_event(&mut self, event: &SessionEvent) -> Result<(), Error> {
+        serde_json::to_writer(&mut self.writer, event)
+            .map_err(|err| Error::io_failed(&err.to_string()))?;
+        self.writer
+            .write_all(b"\n")
+            .map_err(|err| Error::io_failed(&err.to_string()))?;
+
+        if let SessionEvent::Flush { .. } = event {
+            self.writer
+                .flush()
+                .map_err(|err| Error::io_failed(&err.to_string()))?;
+        }
+
+        Ok(())
+    }
+
+    /// Flushes the rest of the events, returns the first failed write if any.
+    pub fn finish(mut self) -> Result<(), Error> {
+        if let Some(err) = self.error.take() {
+            return Err(err);
+        }
+
+        self.writer
+            .flush()
+            .map_err(|err| Error::io_failed(&err.to_string()))
+    }
+}
+
+/// Feeds `session` into a fresh world through the same entry points the host uses,
+/// with `assert_streams` every flush compares the produced command streams with
+/// the recorded ones and fails with `StatusCode::ReplayMismatch` on the first difference.
+pub fn replay(session: &Session, assert_streams: bool) -> Result<(), Error> {
+    let mut application_state = ptr::null_mut();
+    check_status(unsafe { crate::init_world(&mut application_state) })?;
+
+    // The world is destroyed only after the replay.
+    let result = unsafe { replay_events(application_state, session, assert_streams) };
+
+    check_status(unsafe { crate::destroy_world(application_state) })?;
+    result
+}
+
+pub fn replay_file(path: &Path, assert_streams: bool) -> Result<(), Error> {
+    replay(&Session::load(path)?, assert_streams)
+}
+
+/// # Safety
+///
+/// `application_state` must be a handle returned by `init_world` that hasn't
+/// been destroyed yet.
+unsafe fn replay_events(
+    application_state: *mut ApplicationState,
+    session: &Session,
+    assert_streams: bool,
+) -> Result<(), Error> {
+    let mut frame = 0;
+
+    for event in session.events.iter() {
+        match event {
+            SessionEvent::RequestCommands { commands } => {
+                let data = serde_json::to_string(commands)
+                    .map_err(|err| Error::serialization_failed(&err.to_string()))?;
+
+                check_status(crate::send_request_commands(
+                    application_state,
+                    SerializeFormat::Json,
+                    RawBuffer::from_string(&data),
+                ))?;
+            }
+            SessionEvent::TextSize { size } => {
+                crate::push_text_size(crate::get_application_state(application_state)?, *size)
+            }
+            SessionEvent::RenderStateFlush => {
+                crate::render_state_flush(crate::get_application_state(application_state)?)
+            }
+            SessionEvent::FrameStart { time } => {
+                check_status(crate::frame_start(application_state))?;
+
+                let state = crate::get_application_state(application_state)?;
+                let mut frame_clock = state
+                    .world
+                    .resources
+                    .get_mut::<FrameClock>()
+                    .expect("failed to get frame clock");
+
+                frame_clock.time = *time;
+            }
+            SessionEvent::Step => check_status(crate::step(application_state))?,
+            SessionEvent::RenderPass1 => check_status(crate::render_pass1(application_state))?,
+            SessionEvent::RenderPass2 => check_status(crate::render_pass2(application_state))?,
+            SessionEvent::FrameEnd { frame_elapsed } => {
+                check_status(crate::frame_end(application_state))?;
+
+                let state = crate::get_application_state(application_state)?;
+                crate::get_profile_state(&state.world).frame_elapsed = *frame_elapsed;
+            }
+            SessionEvent::Flush {
+                render_commands,
+                exec_commands,
+            } => {
+                if assert_streams {
+                    let state = crate::get_application_state(application_state)?;
+                    let commands_state = state
+                        .world
+                        .resources
+                        .get::<CommandsState>()
+                        .expect("failed to get commands state");
+
+                    assert_commands(
+                        "render",
+                        frame,
+                        render_commands,
+                        &render_commands_to_json(&commands_state.render_commands)?,
+                    )?;
+                    assert_commands(
+                        "execution",
+                        frame,
+                        exec_commands,
+                        &exec_commands_to_json(&commands_state.exec_commands)?,
+                    )?;
+                }
+
+                check_status(crate::flush(application_state))?;
+                frame += 1;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn assert_commands<T: PartialEq + std::fmt::Debug>(
+    stream: &str,
+    frame: usize,
+    expected: &[JsonCommand<T>],
+    actual: &[JsonCommand<T>],
+) -> Result<(), Error> {
+    let mismatch = expected
+        .iter()
+        .zip(actual.iter())
+        .position(|(expected, actual)| expected != actual);
+
+    let message = match mismatch {
+        Some(index) => format!(
+            "{} command {} mismatch at frame {}: expected {:?}, got {:?}",
+            stream, index, frame, expected[index], actual[index]
+        ),
+        None if expected.len() != actual.len() => format!(
+            "{} commands count mismatch at frame {}: expected {}, got {}",
+            stream,
+            frame,
+            expected.len(),
+            actual.len()
+        ),
+        None => return Ok(()),
+    };
+
+    Err(Error::new(StatusCode::ReplayMismatch, &message))
+}
+
+fn check_status(status: StatusCode) -> Result<(), Error> {
+    if status == StatusCode::Ok {
+        Ok(())
+    }
+    else {
+        let message = crate::get_last_error().data_to_string().unwrap_or_default();
+
+        Err(Error::new(status, &message))
+    }
+}
+
+fn to_json<T>(command_type: T, data: &CommandData) -> Result<JsonCommand<T>, Error> {
+    JsonCommand::new(command_type, data)
+        .map_err(|err| Error::serialization_failed(&format!("invalid command string: {}", err)))
+}
+
+fn render_commands_to_json(
+    commands: &[RenderCommand],
+) -> Result<Vec<JsonCommand<RenderCommandType>>, Error> {
+    commands
+        .iter()
+        .map(|command| to_json(command.command_type, &command.data))
+        .collect()
+}
+
+fn exec_commands_to_json(
+    commands: &[ExecutionCommand],
+) -> Result<Vec<JsonCommand<ExecutionCommandType>>, Error> {
+    commands
+        .iter()
+        .map(|command| to_json(command.command_type, &command.data))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::ffi::CString;
+    use std::fs;
+    use std::process;
+
+    use crate::commands::{Vec2f, Vec2i};
+    use crate::errors::StatusCode;
+    use crate::session::*;
+    use crate::test_utils::TestWorld;
+
+    #[test]
+    fn record_and_replay() {
+        let path = env::temp_dir().join(format!("tech_paws_session_{}.jsonl", process::id()));
+        let c_path = CString::new(path.to_str().unwrap()).unwrap();
+
+        {
+            let mut world = TestWorld::new();
+
+            assert_eq!(StatusCode::Ok, unsafe {
+                crate::start_session_recording(world.handle, c_path.as_ptr())
+            });
+
+            crate::push_set_view_port_size_request_command(world.state(), Vec2i::new(640, 480));
+            world.run_frame();
+
+            crate::push_on_touch_start_request_command(world.state(), 0, 0, Vec2f::new(10.0, 10.0));
+            world.run_frame();
+
+            crate::push_on_touch_move_request_command(world.state(), 16, 0, Vec2f::new(50.0, 30.0));
+            world.run_frame();
+
+            crate::push_on_touch_end_request_command(world.state(), 32, 0, Vec2f::new(50.0, 30.0));
+            world.run_frame();
+
+            // Flushed frames are already in the file while recording.
+            let session = Session::load(&path).unwrap();
+
+            assert_eq!(
+                4,
+                session
+                    .events
+                    .iter()
+                    .filter(|event| matches!(event, SessionEvent::Flush { .. }))
+                    .count()
+            );
+
+            assert_eq!(StatusCode::Ok, unsafe {
+                crate::stop_session_recording(world.handle)
+            });
+        }
+
+        assert_eq!(StatusCode::Ok, unsafe {
+            crate::replay_session(c_path.as_ptr(), true)
+        });
+
+        let mut session = Session::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        let last_flush = session
+            .events
+            .iter_mut()
+            .rev()
+            .find_map(|event| match event {
+                SessionEvent::Flush {
+                    render_commands, ..
+                } => Some(render_commands),
+                _ => None,
+            })
+            .unwrap();
+
+        last_flush.pop();
+
+        assert!(replay(&session, false).is_ok());
+        assert_eq!(
+            StatusCode::ReplayMismatch,
+            replay(&session, true).unwrap_err().status
+        );
+    }
+}
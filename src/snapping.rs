@@ -0,0 +1,224 @@
+use legion::prelude::*;
+
+use crate::commands::{Rect, Vec2f};
+use crate::components::{Camera2DPositionListener, CameraRegistry, GridComponent, SnapState};
+use crate::systems::grid::grid_level;
+
+/// Grid of a camera as it's rendered at the current zoom.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SnapGrid {
+    /// World units between the visible lines, the major step when the minor lines have faded out.
+    pub step: f32,
+    pub pixels_per_unit: f32,
+}
+
+impl SnapGrid {
+    /// Returns `None` for unknown cameras and the grids that aren't rendered.
+    pub fn new(
+        grid: &GridComponent,
+        camera_registry: &CameraRegistry,
+        camera_id: usize,
+    ) -> Option<SnapGrid> {
+        if grid.step <= 0 || grid.min_spacing <= 0.0 {
+            return None;
+        }
+
+        let pixels_per_unit = 1.0 / camera_registry.screen_length_to_world(camera_id, 1.0)?;
+        let level = grid_level(grid, pixels_per_unit);
+        let step = if level.fade > 0.0 {
+            level.minor_step
+        }
+        else {
+            level.major_step
+        };
+
+        Some(SnapGrid {
+            step,
+            pixels_per_unit,
+        })
+    }
+}
+
+/// Offset from `value` to the closest snap target within the threshold.
+fn snap_offset(snap_state: &SnapState, grid: &SnapGrid, value: f32) -> Option<f32> {
+    let mut targets = Vec::with_capacity(2);
+
+    if snap_state.to_lines {
+        targets.push((value / grid.step).round() * grid.step);
+    }
+
+    if snap_state.to_centers {
+        targets.push(((value / grid.step - 0.5).round() + 0.5) * grid.step);
+    }
+
+    targets
+        .into_iter()
+        .map(|target| target - value)
+        .filter(|offset| offset.abs() * grid.pixels_per_unit <= snap_state.threshold)
+        .min_by(|a, b| a.abs().total_cmp(&b.abs()))
+}
+
+/// Snaps the world `point`, the point is returned as is when snapping is disabled.
+pub fn snap_point(snap_state: &SnapState, grid: &SnapGrid, point: Vec2f) -> Vec2f {
+    if !snap_state.is_enabled {
+        return point;
+    }
+
+    Vec2f::new(
+        point.x + snap_offset(snap_state, grid, point.x).unwrap_or(0.0),
+        point.y + snap_offset(snap_state, grid, point.y).unwrap_or(0.0),
+    )
+}
+
+/// Moves the world `rect` keeping its size, along each axis the edge or the center
+/// that is the closest to a snap target is snapped.
+pub fn snap_rect(snap_state: &SnapState, grid: &SnapGrid, rect: Rect) -> Rect {
+    if !snap_state.is_enabled {
+        return rect;
+    }
+
+    let snap_axis = |from: f32, size: f32| {
+        [from, from + size / 2.0, from + size]
+            .iter()
+            .filter_map(|value| snap_offset(snap_state, grid, *value))
+            .min_by(|a, b| a.abs().total_cmp(&b.abs()))
+            .unwrap_or(0.0)
+    };
+
+    let offset = Vec2f::new(
+        snap_axis(rect.pos.x, rect.size.x),
+        snap_axis(rect.pos.y, rect.size.y),
+    );
+
+    Rect::new(rect.pos + offset, rect.size)
+}
+
+/// Snaps the world `point` to the grid of the camera with `camera_id`,
+/// the point is returned as is when the camera has no grid.
+pub fn snap_world_point(world: &mut World, camera_id: usize, point: Vec2f) -> Vec2f {
+    match snap_context(world, camera_id) {
+        Some((snap_state, grid)) => snap_point(&snap_state, &grid, point),
+        None => point,
+    }
+}
+
+/// Snaps the world `rect` to the grid of the camera with `camera_id`,
+/// the rect is returned as is when the camera has no grid.
+pub fn snap_world_rect(world: &mut World, camera_id: usize, rect: Rect) -> Rect {
+    match snap_context(world, camera_id) {
+        Some((snap_state, grid)) => snap_rect(&snap_state, &grid, rect),
+        None => rect,
+    }
+}
+
+fn snap_context(world: &mut World, camera_id: usize) -> Option<(SnapState, SnapGrid)> {
+    let grid = <(Read<GridComponent>, Read<Camera2DPositionListener>)>::query()
+        .iter(world)
+        .find(|(_, camera)| camera.id == camera_id)
+        .map(|(grid, _)| *grid)?;
+
+    let snap_state = *world
+        .resources
+        .get::<SnapState>()
+        .expect("failed to get snap state");
+
+    let camera_registry = world
+        .resources
+        .get::<CameraRegistry>()
+        .expect("failed to get camera registry");
+
+    let grid = SnapGrid::new(&grid, &camera_registry, camera_id)?;
+
+    Some((snap_state, grid))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::{Rect, Vec2f};
+    use crate::components::SnapState;
+    use crate::errors::StatusCode;
+    use crate::snapping::{snap_point, snap_rect, snap_world_point, SnapGrid};
+    use crate::test_utils::TestWorld;
+
+    const GRID: SnapGrid = SnapGrid {
+        step: 16.0,
+        pixels_per_unit: 2.0,
+    };
+
+    #[test]
+    fn snap_to_lines() {
+        let snap_state = SnapState::default();
+
+        // 3 world units are 6 screen pixels, 5 units are over the threshold.
+        assert_eq!(
+            Vec2f::new(-16.0, 37.0),
+            snap_point(&snap_state, &GRID, Vec2f::new(-19.0, 37.0))
+        );
+
+        let snap_state = SnapState {
+            is_enabled: false,
+            ..snap_state
+        };
+
+        assert_eq!(
+            Vec2f::new(-19.0, 37.0),
+            snap_point(&snap_state, &GRID, Vec2f::new(-19.0, 37.0))
+        );
+    }
+
+    #[test]
+    fn snap_to_centers() {
+        let snap_state = SnapState {
+            to_lines: false,
+            to_centers: true,
+            ..SnapState::default()
+        };
+
+        assert_eq!(
+            Vec2f::new(-8.0, 40.0),
+            snap_point(&snap_state, &GRID, Vec2f::new(-10.0, 37.0))
+        );
+
+        let snap_state = SnapState {
+            to_lines: true,
+            ..snap_state
+        };
+
+        assert_eq!(
+            Vec2f::new(-8.0, 48.0),
+            snap_point(&snap_state, &GRID, Vec2f::new(-10.0, 46.0))
+        );
+    }
+
+    #[test]
+    fn snap_rect_edges() {
+        let snap_state = SnapState::default();
+        let rect = Rect::new(Vec2f::new(1.0, 5.0), Vec2f::new(20.0, 9.0));
+
+        // The left and the bottom edges are the closest to the lines.
+        assert_eq!(
+            Rect::new(Vec2f::new(0.0, 7.0), Vec2f::new(20.0, 9.0)),
+            snap_rect(&snap_state, &GRID, rect)
+        );
+    }
+
+    #[test]
+    fn snap_to_grid() {
+        let mut world = TestWorld::with_view_port(640, 480);
+        let ortho = crate::gapi::CAMERA_ORTHO;
+        let point = Vec2f::new(19.0, -30.0);
+
+        world.step();
+
+        {
+            let state = world.state();
+            let snapped = snap_world_point(&mut state.world, ortho, point);
+            assert_eq!(Vec2f::new(16.0, -32.0), snapped);
+        }
+
+        assert_eq!(StatusCode::Ok, world.execute("snap::set_enabled false"));
+
+        let state = world.state();
+        assert_eq!(point, snap_world_point(&mut state.world, ortho, point));
+    }
+}
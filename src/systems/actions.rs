@@ -0,0 +1,131 @@
+use crate::commands::Vec2f;
+use crate::components::{
+    Action, Actions, GestureState, InputBindings, InputLayer, InputTrigger, KeyboardState,
+    LayersState, ScrollState, TouchState,
+};
+use crate::debug_services;
+use crate::debug_services::profile::TimedBlockSink;
+
+use legion::prelude::*;
+
+/// Maps the input of the current frame through `InputBindings`, the spawned
+/// action entities become visible to the systems after the next schedule flush.
+/// Pointer input that doesn't reach the canvas layer doesn't produce actions.
+pub fn input_actions_system(sink: TimedBlockSink) -> Box<dyn Schedulable> {
+    SystemBuilder::new("input_actions_system")
+        .read_resource::<InputBindings>()
+        .read_resource::<KeyboardState>()
+        .read_resource::<ScrollState>()
+        .read_resource::<GestureState>()
+        .read_resource::<TouchState>()
+        .read_resource::<LayersState>()
+        .build(
+            move |command_buffer,
+                  _,
+                  (
+                input_bindings,
+                keyboard_state,
+                scroll_state,
+                gesture_state,
+                touch_state,
+                layers_state,
+            ),
+                  _| {
+                let _block = debug_services::timed_block!(sink.clone(), "input_actions_system");
+
+                let mut actions = Vec::new();
+
+                for binding in input_bindings.bindings.iter() {
+                    let action = |pos: Vec2f, value: Vec2f| {
+                        (
+                            Actions,
+                            Action {
+                                name: binding.action.clone(),
+                                pos,
+                                value,
+                            },
+                        )
+                    };
+
+                    match binding.trigger {
+                        InputTrigger::KeyPressed {
+                            key_code,
+                            modifiers,
+                        } => {
+                            if keyboard_state.is_key_pressed(key_code)
+                                && keyboard_state.modifiers == modifiers
+                            {
+                                actions.push(action(touch_state.pos, Vec2f::ZERO));
+                            }
+                        }
+                        InputTrigger::Scroll { .. } | InputTrigger::Gesture(_)
+                            if !layers_state.receives_pointer(InputLayer::Canvas) => {}
+                        InputTrigger::Scroll { modifiers } => {
+                            if scroll_state.delta != Vec2f::ZERO
+                                && scroll_state.modifiers == modifiers
+                            {
+                                actions.push(action(touch_state.pos, scroll_state.delta));
+                            }
+                        }
+                        InputTrigger::Gesture(kind) => {
+                            for gesture in gesture_state.gestures.iter() {
+                                if gesture.kind() == kind {
+                                    actions.push(action(gesture.pos(), gesture.value()));
+                                }
+                            }
+                        }
+                    }
+                }
+
+                if !actions.is_empty() {
+                    command_buffer.insert((), actions);
+                }
+            },
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use legion::prelude::*;
+
+    use crate::commands::Vec2f;
+    use crate::components::{Action, ACTION_ZOOM_IN, KEY_MODIFIER_CTRL};
+    use crate::errors::StatusCode;
+    use crate::test_utils::TestWorld;
+
+    fn action_names(world: &mut TestWorld) -> Vec<String> {
+        let state = world.state();
+        let query = <(Read<Action>,)>::query();
+
+        query
+            .iter(&mut state.world)
+            .map(|(action,)| action.name.clone())
+            .collect()
+    }
+
+    #[test]
+    fn input_actions() {
+        let mut world = TestWorld::with_view_port(640, 480);
+        let key_plus = 187;
+
+        assert_eq!(
+            StatusCode::Ok,
+            world.execute("input::bind_key 187 2 \"zoom_in\"")
+        );
+
+        crate::push_on_key_down_request_command(world.state(), key_plus, KEY_MODIFIER_CTRL);
+        assert_eq!(StatusCode::Ok, unsafe { crate::step(world.handle) });
+        assert_eq!((Vec2f::new(-400.0, -300.0), 1.25), world.camera_transform());
+        assert_eq!(vec![String::from(ACTION_ZOOM_IN)], action_names(&mut world));
+
+        world.end_frame();
+        assert!(action_names(&mut world).is_empty());
+
+        assert_eq!(StatusCode::Ok, world.execute("input::unbind \"zoom_in\""));
+
+        crate::push_on_key_up_request_command(world.state(), key_plus, 0);
+        crate::push_on_key_down_request_command(world.state(), key_plus, KEY_MODIFIER_CTRL);
+        assert_eq!(StatusCode::Ok, unsafe { crate::step(world.handle) });
+        assert_eq!((Vec2f::new(-400.0, -300.0), 1.25), world.camera_transform());
+    }
+}
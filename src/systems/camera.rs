@@ -1,35 +1,149 @@
-use crate::commands::{CommandsState, Vec2f};
-use crate::components::{Camera2D, Camera2DPositionListener, ViewPortSize};
+use crate::commands::CommandsState;
+use crate::components::{Camera2D, CameraRegistry, ViewPortSize};
 use crate::debug_services;
+use crate::debug_services::profile::TimedBlockSink;
 use crate::gapi;
-use crate::memory;
 
 use legion::prelude::*;
 
-pub fn camera_system() -> Box<dyn Schedulable> {
+/// Updates `CameraRegistry` and tells the host about the removed cameras,
+/// the changed view ports and the transforms of every `Camera2D`.
+pub fn camera_system(sink: TimedBlockSink) -> Box<dyn Schedulable> {
     SystemBuilder::new("camera_system")
         .write_resource::<CommandsState>()
+        .write_resource::<CameraRegistry>()
         .read_resource::<ViewPortSize>()
         .with_query(<(Read<Camera2D>,)>::query())
-        .with_query(<(Write<Camera2DPositionListener>,)>::query())
-        .build(|_, mut world, (commands_state, view_port_size), (q1, q2)| {
-            debug_services::timed_block!("camera_system");
-
-            let memory_state = &mut memory::get_memory_state();
-            let mut pos = memory::frame_alloc_vec::<Vec2f>(memory_state);
-            pos.resize(gapi::CAMERA_COUNT, Vec2f::ZERO);
-
-            for (camera,) in q1.iter(&mut world) {
-                pos[camera.id] = Vec2f::new(
-                    view_port_size.width as f32 / 2.0 + camera.pos.x,
-                    view_port_size.height as f32 / 2.0 + camera.pos.y,
-                );
-                gapi::set_camera(commands_state, camera.id);
-                gapi::update_camera_position(commands_state, camera.id, pos[camera.id]);
-            }
-
-            for (mut camera_listener,) in q2.iter(&mut world) {
-                camera_listener.pos = pos[camera_listener.id];
-            }
-        })
+        .build(
+            move |_, world, (commands_state, camera_registry, view_port_size), query| {
+                let _block = debug_services::timed_block!(sink.clone(), "camera_system");
+
+                for id in camera_registry.drain_removed() {
+                    gapi::remove_camera(commands_state, id);
+                }
+
+                camera_registry.update_view_ports(view_port_size);
+
+                for entry in camera_registry.iter_mut() {
+                    if entry.sent_view_port != Some(entry.view_port_rect) {
+                        gapi::update_camera_view_port(
+                            commands_state,
+                            entry.id,
+                            entry.view_port_rect,
+                        );
+                        entry.sent_view_port = Some(entry.view_port_rect);
+                    }
+                }
+
+                for (camera,) in query.iter(world) {
+                    if let Some(transform) = camera_registry.update_camera(&camera, view_port_size)
+                    {
+                        gapi::set_camera(commands_state, camera.id);
+                        gapi::update_camera_transform(commands_state, camera.id, transform);
+
+                        // NOTE: Deprecated, kept for one release so the hosts that don't
+                        // handle `UpdateCameraTransform` yet keep moving the camera.
+                        gapi::update_camera_position(
+                            commands_state,
+                            camera.id,
+                            view_port_size.center() + camera.pos,
+                        );
+                    }
+                }
+            },
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::{ExecutionCommandType, Rect, Vec2f};
+    use crate::components::{
+        Camera2D, CameraMovable2D, CameraRegistry, CameraViewPort, TouchState, YAxis,
+    };
+    use crate::test_utils::TestWorld;
+
+    #[test]
+    fn split_view() {
+        let mut world = TestWorld::with_view_port(640, 480);
+        let left = Rect::new(Vec2f::ZERO, Vec2f::new(320.0, 480.0));
+        let right = Rect::new(Vec2f::new(320.0, 0.0), Vec2f::new(320.0, 480.0));
+
+        let id = {
+            let state = world.state();
+            let id = {
+                let mut camera_registry =
+                    state.world.resources.get_mut::<CameraRegistry>().unwrap();
+                let ortho = camera_registry.get_mut(crate::gapi::CAMERA_ORTHO).unwrap();
+                ortho.view_port = CameraViewPort::Rect(left);
+                camera_registry.create(CameraViewPort::Rect(right))
+            };
+
+            state.world.insert(
+                (),
+                vec![(
+                    Camera2D {
+                        id,
+                        pos: Vec2f::ZERO,
+                        zoom: 1.0,
+                        rotation: 0.0,
+                        y_axis: YAxis::Down,
+                    },
+                    CameraMovable2D::default(),
+                    TouchState::default(),
+                )],
+            );
+
+            id
+        };
+
+        assert_eq!(2, id);
+
+        let exec_commands = world.step();
+        let view_port = exec_commands
+            .windows(4)
+            .find(|commands| {
+                commands[3].command_type == ExecutionCommandType::UpdateCameraViewPort
+                    && commands[0].data.int32 == id as i32
+            })
+            .unwrap();
+
+        assert_eq!(right.pos, view_port[1].data.vec2f);
+        assert_eq!(right.size, view_port[2].data.vec2f);
+
+        // The deprecated position follows every transform.
+        assert!(exec_commands.windows(3).any(|commands| {
+            commands[2].command_type == ExecutionCommandType::UpdateCameraPosition
+                && commands[0].data.int32 == id as i32
+                && commands[1].data.vec2f == Vec2f::new(320.0, 240.0)
+        }));
+
+        // Only the camera under the pointer is dragged.
+        crate::push_on_touch_start_request_command(world.state(), 0, 0, Vec2f::new(500.0, 300.0));
+        world.step();
+        crate::push_on_touch_move_request_command(world.state(), 10, 0, Vec2f::new(510.0, 300.0));
+        world.step();
+        crate::push_on_touch_end_request_command(world.state(), 20, 0, Vec2f::new(510.0, 300.0));
+        world.step();
+
+        assert_eq!(Vec2f::new(10.0, 0.0), world.camera_pos(id));
+        assert_eq!(
+            Vec2f::new(-320.0, -240.0),
+            world.camera_pos(crate::gapi::CAMERA_ORTHO)
+        );
+
+        {
+            let state = world.state();
+            let mut camera_registry = state.world.resources.get_mut::<CameraRegistry>().unwrap();
+
+            assert!(camera_registry.remove(id));
+            assert!(!camera_registry.remove(id));
+        }
+
+        let exec_commands = world.step();
+
+        assert!(exec_commands.windows(2).any(|commands| {
+            commands[1].command_type == ExecutionCommandType::RemoveCamera
+                && commands[0].data.int32 == id as i32
+        }));
+    }
 }
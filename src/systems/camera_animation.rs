@@ -0,0 +1,313 @@
+use crate::commands::{Rect, Vec2f};
+use crate::components::{
+    Camera2D, CameraAnimation2D, CameraBookmarks, CameraBounds2D, CameraMovable2D, CameraPose,
+    CameraRegistry, CameraTarget, CameraTween, FrameClock, ViewPortSize, WorkAreaComponent,
+};
+use crate::debug_services;
+use crate::debug_services::profile::TimedBlockSink;
+use crate::systems::move_camera::{MAX_ZOOM, MIN_ZOOM};
+
+use legion::prelude::*;
+
+/// Screen pixels between the view port edges and the fitted work area.
+pub const FIT_PADDING: f32 = 32.0;
+
+/// Resolves `CameraAnimation2D.target` into a tween from the current pose and
+/// moves the camera along it. Has to run after the systems that move the camera
+/// by the user input, a camera dragged by the user stops animating. The target
+/// is clamped to `CameraBounds2D`, so the camera settles where the bounds allow.
+pub fn camera_animation_system(sink: TimedBlockSink) -> Box<dyn Schedulable> {
+    SystemBuilder::new("camera_animation_system")
+        .read_resource::<ViewPortSize>()
+        .read_resource::<CameraRegistry>()
+        .read_resource::<FrameClock>()
+        .with_query(<(Read<WorkAreaComponent>,)>::query())
+        .with_query(<(
+            Write<Camera2D>,
+            Write<CameraAnimation2D>,
+            Write<CameraMovable2D>,
+            TryRead<CameraBounds2D>,
+        )>::query())
+        .build(
+            move |_,
+                  world,
+                  (view_port_size, camera_registry, frame_clock),
+                  (work_area_query, camera_query)| {
+                let _block = debug_services::timed_block!(sink.clone(), "camera_animation_system");
+
+                let work_area_size = work_area_query
+                    .iter(world)
+                    .next()
+                    .map(|(work_area,)| work_area.size)
+                    .unwrap_or(Vec2f::ZERO);
+
+                for (mut camera, mut animation, mut camera_movable, bounds) in
+                    camera_query.iter(world)
+                {
+                    if camera_movable.is_hot {
+                        animation.target = None;
+                        animation.tween = None;
+                        continue;
+                    }
+
+                    if let Some(target) = animation.target.take() {
+                        let view_port = camera_registry.view_port_rect(camera.id, view_port_size);
+                        let mut to = resolve_target(&camera, view_port, work_area_size, target);
+
+                        if let Some(bounds) = bounds {
+                            to = clamp_to_bounds(&camera, to, &bounds, view_port, work_area_size);
+                        }
+
+                        animation.tween = Some(CameraTween {
+                            from: CameraPose::of(&camera),
+                            to,
+                            start: frame_clock.time,
+                        });
+                        camera_movable.velocity = Vec2f::ZERO;
+                    }
+
+                    let tween = match animation.tween {
+                        Some(tween) => tween,
+                        None => continue,
+                    };
+
+                    let elapsed = frame_clock.time.saturating_sub(tween.start);
+                    let t = if animation.duration.is_zero() {
+                        1.0
+                    }
+                    else {
+                        elapsed.as_secs_f32() / animation.duration.as_secs_f32()
+                    };
+
+                    if t >= 1.0 {
+                        tween.to.apply(&mut camera);
+                        animation.tween = None;
+                    }
+                    else {
+                        let pose = tween.from.lerp(&tween.to, animation.easing.apply(t));
+                        pose.apply(&mut camera);
+                    }
+                }
+            },
+        )
+}
+
+fn resolve_target(
+    camera: &Camera2D,
+    view_port: Rect,
+    work_area_size: Vec2f,
+    target: CameraTarget,
+) -> CameraPose {
+    match target {
+        CameraTarget::FitWorkArea => {
+            let available = view_port.size - Vec2f::new(FIT_PADDING, FIT_PADDING) * 2.0;
+            let zoom = if work_area_size.x > 0.0 && work_area_size.y > 0.0 {
+                (available.x / work_area_size.x).min(available.y / work_area_size.y)
+            }
+            else {
+                1.0
+            };
+
+            center_on(
+                camera,
+                zoom.clamp(MIN_ZOOM, MAX_ZOOM),
+                0.0,
+                work_area_size / 2.0,
+            )
+        }
+        CameraTarget::CenterOn(point) => center_on(camera, camera.zoom, camera.rotation, point),
+        CameraTarget::Pose(pose) => CameraPose {
+            zoom: pose.zoom.clamp(MIN_ZOOM, MAX_ZOOM),
+            ..pose
+        },
+    }
+}
+
+fn clamp_to_bounds(
+    camera: &Camera2D,
+    pose: CameraPose,
+    bounds: &CameraBounds2D,
+    view_port: Rect,
+    work_area_size: Vec2f,
+) -> CameraPose {
+    let mut camera = *camera;
+    pose.apply(&mut camera);
+
+    let (min, max) = bounds.limits(&camera, view_port, work_area_size);
+
+    CameraPose {
+        pos: Vec2f::new(
+            pose.pos.x.clamp(min.x, max.x),
+            pose.pos.y.clamp(min.y, max.y),
+        ),
+        ..pose
+    }
+}
+
+/// Pose with the world `point` at the view port center.
+fn center_on(camera: &Camera2D, zoom: f32, rotation: f32, point: Vec2f) -> CameraPose {
+    let camera = Camera2D {
+        pos: Vec2f::ZERO,
+        zoom,
+        rotation,
+        ..*camera
+    };
+
+    CameraPose {
+        pos: camera.transform(Rect::ZERO).apply_vector(point) * -1.0,
+        zoom,
+        rotation,
+    }
+}
+
+/// Starts animating the camera with `id` to `target`, returns false
+/// if there is no animated camera with `id`.
+pub fn animate_camera(world: &mut World, id: usize, target: CameraTarget) -> bool {
+    let query = <(Read<Camera2D>, Write<CameraAnimation2D>)>::query();
+
+    for (camera, mut animation) in query.iter(world) {
+        if camera.id == id {
+            animation.target = Some(target);
+            return true;
+        }
+    }
+
+    false
+}
+
+/// Saves the current pose of the camera with `id`, returns false if there is no such camera.
+pub fn save_camera_bookmark(world: &mut World, id: usize, name: &str) -> bool {
+    let pose = <(Read<Camera2D>,)>::query()
+        .iter(world)
+        .find(|(camera,)| camera.id == id)
+        .map(|(camera,)| CameraPose::of(&camera));
+
+    let pose = match pose {
+        Some(pose) => pose,
+        None => return false,
+    };
+
+    world
+        .resources
+        .get_mut::<CameraBookmarks>()
+        .expect("failed to get camera bookmarks")
+        .bookmarks
+        .insert(String::from(name), pose);
+
+    true
+}
+
+/// Animates the camera with `id` to the saved pose, returns false
+/// if there is no such bookmark or animated camera.
+pub fn load_camera_bookmark(world: &mut World, id: usize, name: &str) -> bool {
+    let pose = world
+        .resources
+        .get::<CameraBookmarks>()
+        .expect("failed to get camera bookmarks")
+        .bookmarks
+        .get(name)
+        .copied();
+
+    match pose {
+        Some(pose) => animate_camera(world, id, CameraTarget::Pose(pose)),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::Vec2f;
+    use crate::errors::StatusCode;
+    use crate::test_utils::{last_error, TestWorld};
+
+    #[test]
+    fn camera_animation() {
+        let mut world = TestWorld::with_view_port(640, 480);
+        let ortho = crate::gapi::CAMERA_ORTHO as i32;
+        let fit_zoom = 416.0 / 480.0;
+        let fit_pos = Vec2f::new(320.0, 240.0) * -fit_zoom;
+        let is_close = |(pos, zoom): (Vec2f, f32), expected_pos: Vec2f, expected_zoom: f32| {
+            (pos - expected_pos).length() < 1e-3 && (zoom - expected_zoom).abs() < 1e-5
+        };
+
+        crate::push_camera_fit_request_command(world.state(), ortho);
+        world.step();
+        assert!(is_close(
+            world.camera_transform(),
+            Vec2f::new(-320.0, -240.0),
+            1.0
+        ));
+
+        world.set_frame_time(150);
+        world.step();
+        let (_, zoom) = world.camera_transform();
+        assert!(zoom < 1.0 && zoom > fit_zoom);
+
+        world.set_frame_time(300);
+        world.step();
+        assert!(is_close(world.camera_transform(), fit_pos, fit_zoom));
+
+        crate::push_camera_save_bookmark_request_command(world.state(), ortho, "fit");
+        world.step();
+
+        assert_eq!(StatusCode::Ok, world.execute("camera::goto 0 -10"));
+        world.step();
+        world.set_frame_time(600);
+        world.step();
+        assert!(is_close(
+            world.camera_transform(),
+            Vec2f::new(0.0, 10.0 * fit_zoom),
+            fit_zoom
+        ));
+
+        crate::push_camera_load_bookmark_request_command(world.state(), ortho, "fit");
+        world.step();
+        world.set_frame_time(900);
+        world.step();
+        assert!(is_close(world.camera_transform(), fit_pos, fit_zoom));
+
+        assert_eq!(
+            StatusCode::CommandFailed,
+            world.execute("camera::load \"x\"")
+        );
+        assert_eq!("Bookmark 'x' not found", last_error());
+
+        // Dragging the camera cancels the animation.
+        crate::push_camera_go_to_request_command(world.state(), ortho, Vec2f::ZERO);
+        world.step();
+        world.set_frame_time(1000);
+        world.step();
+        crate::push_on_touch_start_request_command(world.state(), 0, 0, Vec2f::new(400.0, 300.0));
+        world.set_frame_time(1100);
+        world.step();
+        crate::push_on_touch_end_request_command(world.state(), 0, 0, Vec2f::new(400.0, 300.0));
+        world.step();
+        let (pos, _) = world.camera_transform();
+
+        world.set_frame_time(1300);
+        world.step();
+        assert_eq!(pos, world.camera_transform().0);
+        assert!((pos - fit_pos).length() > 1.0 && pos.length() > 1.0);
+    }
+
+    #[test]
+    fn go_to_out_of_bounds() {
+        let mut world = TestWorld::with_view_port(640, 480);
+        let ortho = crate::gapi::CAMERA_ORTHO as i32;
+
+        // The work area can't leave the view port further than to the 64 pixels margin.
+        crate::push_camera_go_to_request_command(world.state(), ortho, Vec2f::new(5000.0, 0.0));
+        world.step();
+
+        world.set_frame_time(150);
+        world.step();
+        let (pos, _) = world.camera_transform();
+        assert!(pos.x < -320.0 && pos.x > -896.0);
+
+        for time in [300, 400, 1000] {
+            world.set_frame_time(time);
+            world.step();
+            assert_eq!(Vec2f::new(-896.0, 0.0), world.camera_transform().0);
+        }
+    }
+}
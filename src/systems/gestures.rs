@@ -0,0 +1,300 @@
+use std::time::Duration;
+
+use crate::commands::Vec2f;
+use crate::components::{FrameClock, Gesture, GesturePress, GestureState, Touch, TouchState};
+use crate::debug_services;
+use crate::debug_services::profile::TimedBlockSink;
+
+use legion::prelude::*;
+
+/// Has to run before the systems that consume `GestureState`.
+pub fn gesture_system(sink: TimedBlockSink) -> Box<dyn Schedulable> {
+    SystemBuilder::new("gesture_system")
+        .write_resource::<GestureState>()
+        .read_resource::<TouchState>()
+        .read_resource::<FrameClock>()
+        .build(move |_, _, (gesture_state, touch_state, frame_clock), _| {
+            let _block = debug_services::timed_block!(sink.clone(), "gesture_system");
+            recognize(gesture_state, touch_state, frame_clock.time);
+        })
+}
+
+fn recognize(state: &mut GestureState, touch_state: &TouchState, time: Duration) {
+    state.gestures.clear();
+
+    if touch_state.touch == Touch::Start {
+        if let Some(press) = state.press.take() {
+            release(state, &press, time);
+        }
+    }
+
+    // NOTE: Start may be overwritten by the following move or end during the same frame.
+    if state.press.is_none()
+        && (touch_state.primary_pointer.is_some() || touch_state.touch == Touch::End)
+    {
+        state.press = Some(GesturePress::new(touch_state.touch_start, time));
+    }
+
+    let mut press = match state.press {
+        Some(press) => press,
+        None => return,
+    };
+
+    // Several pointers are a pinch, not a gesture of the primary pointer.
+    if touch_state.pointers.len() > 1 && !press.is_cancelled {
+        press.is_cancelled = true;
+
+        if press.is_dragging {
+            state.gestures.push(Gesture::DragEnd {
+                pos: press.last_pos,
+            });
+        }
+    }
+
+    if !press.is_cancelled {
+        move_press(state, &mut press, touch_state.touch_current, time);
+    }
+
+    if touch_state.primary_pointer.is_none() {
+        state.press = None;
+        release(state, &press, time);
+    }
+    else {
+        state.press = Some(press);
+    }
+}
+
+fn move_press(state: &mut GestureState, press: &mut GesturePress, pos: Vec2f, time: Duration) {
+    let config = state.config;
+
+    if !press.is_dragging && (pos - press.start).length() > config.drag_threshold {
+        press.is_dragging = true;
+        state.gestures.push(Gesture::DragStart {
+            start: press.start,
+            pos,
+        });
+    }
+
+    if pos != press.last_pos {
+        let elapsed = time.saturating_sub(press.last_time).as_secs_f32();
+
+        if elapsed > 0.0 {
+            press.velocity = (pos - press.last_pos) / elapsed;
+        }
+
+        if press.is_dragging {
+            state.gestures.push(Gesture::Drag {
+                pos,
+                delta: pos - press.last_pos,
+            });
+        }
+
+        press.last_pos = pos;
+        press.last_time = time;
+    }
+
+    if !press.is_dragging
+        && !press.is_long_pressed
+        && time.saturating_sub(press.start_time) >= config.long_press_duration
+    {
+        press.is_long_pressed = true;
+        state.gestures.push(Gesture::LongPress { pos: press.start });
+    }
+}
+
+fn release(state: &mut GestureState, press: &GesturePress, time: Duration) {
+    let config = state.config;
+
+    if press.is_cancelled || press.is_long_pressed {
+        return;
+    }
+
+    if press.is_dragging {
+        state.gestures.push(Gesture::DragEnd {
+            pos: press.last_pos,
+        });
+
+        if time.saturating_sub(press.last_time) <= config.fling_max_pause
+            && press.velocity.length() >= config.fling_min_velocity
+        {
+            state.gestures.push(Gesture::Fling {
+                pos: press.last_pos,
+                velocity: press.velocity,
+            });
+        }
+
+        return;
+    }
+
+    if time.saturating_sub(press.start_time) > config.tap_max_duration {
+        return;
+    }
+
+    match state.last_tap {
+        Some((pos, tap_time))
+            if time.saturating_sub(tap_time) <= config.double_tap_max_interval
+                && (press.start - pos).length() <= config.double_tap_max_distance =>
+        {
+            state.gestures.push(Gesture::DoubleTap { pos: press.start });
+            state.last_tap = None;
+        }
+        _ => {
+            state.gestures.push(Gesture::Tap { pos: press.start });
+            state.last_tap = Some((press.start, time));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::commands::Vec2f;
+    use crate::components::{Gesture, GestureState, Touch, TouchState};
+    use crate::systems::gestures::recognize;
+
+    struct Recognizer {
+        gesture_state: GestureState,
+        touch_state: TouchState,
+        time: Duration,
+    }
+
+    impl Recognizer {
+        fn new() -> Recognizer {
+            Recognizer {
+                gesture_state: GestureState::default(),
+                touch_state: TouchState::default(),
+                time: Duration::default(),
+            }
+        }
+
+        fn frame(&mut self, millis: u64) -> Vec<Gesture> {
+            self.time += Duration::from_millis(millis);
+            recognize(&mut self.gesture_state, &self.touch_state, self.time);
+            self.touch_state.touch = Touch::None;
+            self.gesture_state.gestures.clone()
+        }
+    }
+
+    #[test]
+    fn tap_and_double_tap() {
+        let mut recognizer = Recognizer::new();
+        let pos = Vec2f::new(10.0, 10.0);
+
+        recognizer
+            .touch_state
+            .on_touch_start(0, pos, recognizer.time);
+        assert!(recognizer.frame(16).is_empty());
+        recognizer.touch_state.on_touch_end(0, pos, recognizer.time);
+        assert_eq!(vec![Gesture::Tap { pos }], recognizer.frame(16));
+
+        recognizer
+            .touch_state
+            .on_touch_start(0, pos, recognizer.time);
+        recognizer.touch_state.on_touch_end(0, pos, recognizer.time);
+        assert_eq!(vec![Gesture::DoubleTap { pos }], recognizer.frame(100));
+
+        recognizer
+            .touch_state
+            .on_touch_start(0, pos, recognizer.time);
+        recognizer.touch_state.on_touch_end(0, pos, recognizer.time);
+        assert_eq!(vec![Gesture::Tap { pos }], recognizer.frame(400));
+    }
+
+    #[test]
+    fn long_press() {
+        let mut recognizer = Recognizer::new();
+        let pos = Vec2f::new(10.0, 10.0);
+
+        recognizer
+            .touch_state
+            .on_touch_start(0, pos, recognizer.time);
+        assert!(recognizer.frame(16).is_empty());
+        assert!(recognizer.frame(300).is_empty());
+        assert_eq!(vec![Gesture::LongPress { pos }], recognizer.frame(300));
+        assert!(recognizer.frame(300).is_empty());
+
+        recognizer.touch_state.on_touch_end(0, pos, recognizer.time);
+        assert!(recognizer.frame(16).is_empty());
+    }
+
+    #[test]
+    fn drag_and_fling() {
+        let mut recognizer = Recognizer::new();
+        let start = Vec2f::new(10.0, 10.0);
+        let pos = Vec2f::new(14.0, 10.0);
+
+        recognizer
+            .touch_state
+            .on_touch_start(0, start, recognizer.time);
+        assert!(recognizer.frame(10).is_empty());
+
+        recognizer
+            .touch_state
+            .on_touch_move(0, pos, recognizer.time);
+        assert!(recognizer.frame(10).is_empty());
+
+        let delta = Vec2f::new(16.0, 0.0);
+        recognizer
+            .touch_state
+            .on_touch_move(0, pos + delta, recognizer.time);
+        assert_eq!(
+            vec![
+                Gesture::DragStart {
+                    start,
+                    pos: pos + delta
+                },
+                Gesture::Drag {
+                    pos: pos + delta,
+                    delta
+                },
+            ],
+            recognizer.frame(10)
+        );
+
+        recognizer
+            .touch_state
+            .on_touch_end(0, pos + delta, recognizer.time);
+        assert_eq!(
+            vec![
+                Gesture::DragEnd { pos: pos + delta },
+                Gesture::Fling {
+                    pos: pos + delta,
+                    velocity: Vec2f::new(1600.0, 0.0)
+                },
+            ],
+            recognizer.frame(10)
+        );
+    }
+
+    #[test]
+    fn second_pointer_cancels_drag() {
+        let mut recognizer = Recognizer::new();
+        let start = Vec2f::new(10.0, 10.0);
+        let pos = Vec2f::new(50.0, 10.0);
+
+        recognizer
+            .touch_state
+            .on_touch_start(0, start, recognizer.time);
+        recognizer
+            .touch_state
+            .on_touch_move(0, pos, recognizer.time);
+        assert_eq!(2, recognizer.frame(10).len());
+
+        recognizer
+            .touch_state
+            .on_touch_start(1, Vec2f::new(100.0, 100.0), recognizer.time);
+        assert_eq!(vec![Gesture::DragEnd { pos }], recognizer.frame(10));
+
+        recognizer
+            .touch_state
+            .on_touch_end(1, Vec2f::new(100.0, 100.0), recognizer.time);
+        recognizer
+            .touch_state
+            .on_touch_move(0, start, recognizer.time);
+        recognizer
+            .touch_state
+            .on_touch_end(0, start, recognizer.time);
+        assert!(recognizer.frame(10).is_empty());
+    }
+}
@@ -1,55 +1,255 @@
-use crate::commands::CommandsState;
-use crate::components::{Camera2DPositionListener, GridComponent, ViewPortSize};
+use crate::commands::{Color, CommandsState, Rect, Vec2f};
+use crate::components::{Camera2DPositionListener, CameraRegistry, GridComponent, GridStyle};
 use crate::debug_services;
+use crate::debug_services::profile::TimedBlockSink;
 use crate::gapi;
 
 use legion::prelude::*;
 
-pub fn render_grid_system() -> Box<dyn Schedulable> {
+use std::ops::RangeInclusive;
+
+/// Screen size of the dots of `GridStyle::Dots`.
+pub const GRID_DOT_SIZE: f32 = 2.0;
+
+/// Grid level that fits the camera zoom.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GridLevel {
+    pub minor_step: f32,
+    pub major_step: f32,
+    /// Opacity of the minor lines from 0 to 1.
+    pub fade: f32,
+}
+
+pub fn grid_level(grid: &GridComponent, pixels_per_unit: f32) -> GridLevel {
+    let subdivisions = grid.subdivisions.max(2) as f32;
+    let spacing = grid.step as f32 * pixels_per_unit;
+    let level = (grid.min_spacing / spacing).log(subdivisions).ceil();
+    let minor_step = grid.step as f32 * subdivisions.powf(level);
+    let minor_spacing = minor_step * pixels_per_unit;
+
+    GridLevel {
+        minor_step,
+        major_step: minor_step * subdivisions,
+        fade: ((minor_spacing - grid.min_spacing) / grid.min_spacing).clamp(0.0, 1.0),
+    }
+}
+
+pub fn render_grid_system(sink: TimedBlockSink) -> Box<dyn Schedulable> {
     SystemBuilder::new("grid_system")
         .write_resource::<CommandsState>()
-        .read_resource::<ViewPortSize>()
+        .read_resource::<CameraRegistry>()
         .with_query(<(Read<GridComponent>, Read<Camera2DPositionListener>)>::query())
-        .build(|_, world, (commands_state, view_port_size), query| {
-            debug_services::timed_block!("grid_system");
+        .build(move |_, world, (commands_state, camera_registry), query| {
+            let _block = debug_services::timed_block!(sink.clone(), "grid_system");
 
             for (grid, camera) in query.iter(world) {
-                gapi::push_color_shader(commands_state);
-                gapi::push_color(commands_state, grid.color);
-                gapi::set_color_uniform(commands_state);
+                if grid.step <= 0 || grid.min_spacing <= 0.0 {
+                    continue;
+                }
 
-                push_lines(commands_state, &grid, &view_port_size, &camera);
-            }
+                let (visible, units_per_pixel) = match (
+                    camera_registry.visible_world_rect(camera.id),
+                    camera_registry.screen_length_to_world(camera.id, 1.0),
+                ) {
+                    (Some(visible), Some(units_per_pixel)) => (visible, units_per_pixel),
+                    _ => continue,
+                };
+
+                let level = grid_level(&grid, 1.0 / units_per_pixel);
+                let minor_color = Color {
+                    a: grid.minor_color.a * level.fade,
+                    ..grid.minor_color
+                };
+
+                match grid.style {
+                    GridStyle::Lines => {
+                        if minor_color.a > 0.0 {
+                            begin_batch(commands_state, minor_color);
+                            push_lines(commands_state, visible, level.minor_step, |i| {
+                                i % grid.subdivisions.max(2) as i64 != 0
+                            });
+                            end_batch(commands_state, camera.id, GridStyle::Lines);
+                        }
+
+                        begin_batch(commands_state, grid.major_color);
+                        push_lines(commands_state, visible, level.major_step, |i| i != 0);
+                        end_batch(commands_state, camera.id, GridStyle::Lines);
+                    }
+                    GridStyle::Dots => {
+                        let dot_size = GRID_DOT_SIZE * units_per_pixel;
+                        let subdivisions = grid.subdivisions.max(2) as i64;
 
-            gapi::set_camera(commands_state, gapi::CAMERA_ORTHO);
-            gapi::draw_lines(commands_state);
+                        if minor_color.a > 0.0 {
+                            begin_batch(commands_state, minor_color);
+                            push_dots(
+                                commands_state,
+                                visible,
+                                level.minor_step,
+                                dot_size,
+                                |i, j| i % subdivisions != 0 || j % subdivisions != 0,
+                            );
+                            end_batch(commands_state, camera.id, GridStyle::Dots);
+                        }
+
+                        begin_batch(commands_state, grid.major_color);
+                        push_dots(
+                            commands_state,
+                            visible,
+                            level.major_step,
+                            dot_size,
+                            |_, _| true,
+                        );
+                        end_batch(commands_state, camera.id, GridStyle::Dots);
+                    }
+                }
+
+                begin_batch(commands_state, grid.axis_color);
+                push_axes(commands_state, visible);
+                end_batch(commands_state, camera.id, GridStyle::Lines);
+            }
         })
 }
 
-fn push_lines(
-    render_state: &mut CommandsState,
-    grid: &GridComponent,
-    size: &ViewPortSize,
-    camera: &Camera2DPositionListener,
-) {
-    let camera_x = camera.pos.x.round() as i32;
-    let camera_y = camera.pos.y.round() as i32;
+fn begin_batch(commands_state: &mut CommandsState, color: Color) {
+    gapi::push_color_shader(commands_state);
+    gapi::push_color(commands_state, color);
+    gapi::set_color_uniform(commands_state);
+}
 
-    // Vertical lines
-    let from = -camera_x + camera_x % grid.step;
-    let to = -camera_x + size.width;
+fn end_batch(commands_state: &mut CommandsState, camera_id: usize, style: GridStyle) {
+    gapi::set_camera(commands_state, camera_id);
+
+    match style {
+        GridStyle::Lines => gapi::draw_lines(commands_state),
+        GridStyle::Dots => gapi::draw_quads(commands_state),
+    }
+}
+
+/// Indices of the lines `step` apart inside of `from..=to`, the line 0 is at the origin.
+pub(crate) fn line_indices(from: f32, to: f32, step: f32) -> RangeInclusive<i64> {
+    (from / step).ceil() as i64..=(to / step).floor() as i64
+}
+
+/// The host applies the camera transform, lines are placed in the world space
+/// over `visible`, the world rect that covers the camera view port.
+/// Only the lines with the indices accepted by `filter` are pushed.
+fn push_lines<F>(commands_state: &mut CommandsState, visible: Rect, step: f32, filter: F)
+where
+    F: Fn(i64) -> bool,
+{
+    let from = visible.pos;
+    let to = visible.pos + visible.size;
 
-    for i in (from..to).step_by(grid.step as usize) {
-        gapi::push_vec2f_xy(render_state, i as f32, -camera.pos.y);
-        gapi::push_vec2f_xy(render_state, i as f32, size.height as f32 - camera.pos.y);
+    // Vertical lines
+    for i in line_indices(from.x, to.x, step).filter(|i| filter(*i)) {
+        let x = i as f32 * step;
+        gapi::push_vec2f_xy(commands_state, x, from.y);
+        gapi::push_vec2f_xy(commands_state, x, to.y);
     }
 
     // Horizontal lines
-    let from = -camera_y + camera_y % grid.step;
-    let to = -camera_y + size.height;
+    for i in line_indices(from.y, to.y, step).filter(|i| filter(*i)) {
+        let y = i as f32 * step;
+        gapi::push_vec2f_xy(commands_state, from.x, y);
+        gapi::push_vec2f_xy(commands_state, to.x, y);
+    }
+}
+
+/// The world x = 0 and y = 0 lines that cross `visible`.
+fn push_axes(commands_state: &mut CommandsState, visible: Rect) {
+    let from = visible.pos;
+    let to = visible.pos + visible.size;
+
+    if from.x <= 0.0 && to.x >= 0.0 {
+        gapi::push_vec2f_xy(commands_state, 0.0, from.y);
+        gapi::push_vec2f_xy(commands_state, 0.0, to.y);
+    }
+
+    if from.y <= 0.0 && to.y >= 0.0 {
+        gapi::push_vec2f_xy(commands_state, from.x, 0.0);
+        gapi::push_vec2f_xy(commands_state, to.x, 0.0);
+    }
+}
+
+/// Only the dots with the indices accepted by `filter` are pushed.
+fn push_dots<F>(
+    commands_state: &mut CommandsState,
+    visible: Rect,
+    step: f32,
+    dot_size: f32,
+    filter: F,
+) where
+    F: Fn(i64, i64) -> bool,
+{
+    let from = visible.pos;
+    let to = visible.pos + visible.size;
+    let size = Vec2f::new(dot_size, dot_size);
+
+    for i in line_indices(from.x, to.x, step) {
+        for j in line_indices(from.y, to.y, step).filter(|j| filter(i, *j)) {
+            let center = Vec2f::new(i as f32 * step, j as f32 * step);
+            gapi::push_vec2f(commands_state, center - size / 2.0);
+            gapi::push_vec2f(commands_state, size);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::{Color, CommandsState, Rect, Vec2f};
+    use crate::components::{GridComponent, GridStyle};
+    use crate::systems::grid::{grid_level, line_indices, push_dots, GridLevel};
+
+    fn grid() -> GridComponent {
+        GridComponent {
+            step: 16,
+            subdivisions: 4,
+            min_spacing: 8.0,
+            minor_color: Color::default(),
+            major_color: Color::default(),
+            axis_color: Color::default(),
+            style: GridStyle::Lines,
+        }
+    }
+
+    #[test]
+    fn grid_levels() {
+        let grid = grid();
+
+        assert_eq!(
+            GridLevel {
+                minor_step: 16.0,
+                major_step: 64.0,
+                fade: 1.0,
+            },
+            grid_level(&grid, 1.0)
+        );
+
+        // The minor lines fade out before they get closer than 8 pixels.
+        assert_eq!(0.5, grid_level(&grid, 0.75).fade);
+
+        // Zooming out merges the lines, zooming in subdivides them.
+        assert_eq!(64.0, grid_level(&grid, 0.25).minor_step);
+        assert_eq!(0.0, grid_level(&grid, 0.125).fade);
+        assert_eq!(4.0, grid_level(&grid, 4.0).minor_step);
+    }
+
+    #[test]
+    fn lines_with_negative_offsets() {
+        assert_eq!(-2..=1, line_indices(-40.0, 20.0, 16.0));
+        assert_eq!(-3..=-1, line_indices(-50.0, -10.0, 16.0));
+    }
+
+    #[test]
+    fn minor_dots_skip_major() {
+        let mut commands_state = CommandsState::default();
+        let visible = Rect::new(Vec2f::ZERO, Vec2f::new(64.0, 64.0));
+
+        push_dots(&mut commands_state, visible, 16.0, 2.0, |i, j| {
+            i % 4 != 0 || j % 4 != 0
+        });
 
-    for i in (from..to).step_by(grid.step as usize) {
-        gapi::push_vec2f_xy(render_state, -camera.pos.x, i as f32);
-        gapi::push_vec2f_xy(render_state, size.width as f32 - camera.pos.x, i as f32);
+        // 5x5 minor dots without the 4 major ones in the corners, two vertices each.
+        assert_eq!(21 * 2, commands_state.render_commands.len());
     }
 }
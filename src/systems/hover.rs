@@ -0,0 +1,128 @@
+use crate::commands::CommandsState;
+use crate::components::{
+    Bounds, Camera2DPositionListener, CameraRegistry, CursorState, Hoverable, InputLayer,
+    LayersState, TouchState,
+};
+use crate::debug_services;
+use crate::debug_services::profile::TimedBlockSink;
+use crate::gapi;
+use crate::picking;
+
+use legion::prelude::*;
+
+/// Only the topmost entity under the pointer is hovered, and none of them
+/// when the pointer doesn't reach the canvas layer.
+pub fn hover_system(sink: TimedBlockSink) -> Box<dyn Schedulable> {
+    SystemBuilder::new("hover_system")
+        .read_resource::<TouchState>()
+        .read_resource::<LayersState>()
+        .read_resource::<CameraRegistry>()
+        .write_resource::<CursorState>()
+        .with_query(
+            <(Read<Bounds>, Read<Camera2DPositionListener>)>::query()
+                .filter(component::<Hoverable>()),
+        )
+        .with_query(<(Write<Hoverable>,)>::query())
+        .build(
+            move |_,
+                  world,
+                  (touch_state, layers_state, camera_registry, cursor_state),
+                  (bounds_query, hoverable_query)| {
+                let _block = debug_services::timed_block!(sink.clone(), "hover_system");
+
+                let hovered = if !layers_state.receives_pointer(InputLayer::Canvas) {
+                    None
+                }
+                else {
+                    let hits = bounds_query
+                        .iter_entities(world)
+                        .filter(|(_, (bounds, camera))| {
+                            picking::hit_test(camera_registry, camera.id, bounds, touch_state.pos)
+                        })
+                        .map(|(entity, (bounds, _))| (entity, *bounds))
+                        .collect::<Vec<(Entity, Bounds)>>();
+
+                    picking::topmost(hits.iter().map(|(entity, bounds)| (*entity, bounds)))
+                };
+
+                for (entity, (mut hoverable,)) in hoverable_query.iter_entities(world) {
+                    let is_hovered = hovered == Some(entity);
+
+                    hoverable.hover.update(is_hovered);
+
+                    if is_hovered {
+                        cursor_state.cursor = hoverable.cursor;
+                    }
+                }
+            },
+        )
+}
+
+pub fn cursor_system(sink: TimedBlockSink) -> Box<dyn Schedulable> {
+    SystemBuilder::new("cursor_system")
+        .write_resource::<CommandsState>()
+        .write_resource::<CursorState>()
+        .build(move |_, _, (commands_state, cursor_state), _| {
+            let _block = debug_services::timed_block!(sink.clone(), "cursor_system");
+
+            if cursor_state.last_cursor != Some(cursor_state.cursor) {
+                gapi::set_cursor(commands_state, cursor_state.cursor);
+                cursor_state.last_cursor = Some(cursor_state.cursor);
+            }
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use legion::prelude::*;
+
+    use crate::commands::{Cursor, ExecutionCommand, ExecutionCommandType, Vec2f};
+    use crate::components::Hoverable;
+    use crate::test_utils::TestWorld;
+
+    fn cursor(exec_commands: &[ExecutionCommand]) -> Option<i32> {
+        exec_commands.windows(2).find_map(|commands| {
+            if commands[1].command_type == ExecutionCommandType::SetCursor {
+                Some(commands[0].data.int32)
+            }
+            else {
+                None
+            }
+        })
+    }
+
+    #[test]
+    fn hover_cursor() {
+        let mut world = TestWorld::with_view_port(640, 480);
+
+        crate::push_on_touch_move_request_command(world.state(), 0, 0, Vec2f::new(400.0, 300.0));
+        assert_eq!(Some(Cursor::Crosshair as i32), cursor(&world.step()));
+
+        {
+            let state = world.state();
+            let query = <(Read<Hoverable>,)>::query();
+            let (hoverable,) = query.iter(&mut state.world).next().unwrap();
+
+            assert!(hoverable.hover.is_hovered);
+            assert!(hoverable.hover.is_entered);
+        }
+
+        crate::push_on_touch_move_request_command(world.state(), 0, 0, Vec2f::new(410.0, 300.0));
+        assert_eq!(None, cursor(&world.step()));
+
+        crate::push_on_touch_move_request_command(world.state(), 0, 0, Vec2f::new(700.0, 300.0));
+        assert_eq!(Some(Cursor::Grab as i32), cursor(&world.step()));
+
+        // Drags start inside of the camera view port only.
+        crate::push_on_touch_start_request_command(world.state(), 0, 0, Vec2f::new(700.0, 300.0));
+        assert_eq!(None, cursor(&world.step()));
+        crate::push_on_touch_end_request_command(world.state(), 0, 0, Vec2f::new(700.0, 300.0));
+        assert_eq!(None, cursor(&world.step()));
+
+        crate::push_on_touch_start_request_command(world.state(), 0, 0, Vec2f::new(600.0, 300.0));
+        assert_eq!(Some(Cursor::Grabbing as i32), cursor(&world.step()));
+
+        crate::push_on_touch_end_request_command(world.state(), 0, 0, Vec2f::new(600.0, 300.0));
+        assert_eq!(Some(Cursor::Crosshair as i32), cursor(&world.step()));
+    }
+}
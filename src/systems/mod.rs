@@ -1,5 +1,9 @@
 // Systems
+pub mod actions;
 pub mod camera;
+pub mod camera_animation;
+pub mod gestures;
 pub mod grid;
+pub mod hover;
 pub mod move_camera;
 pub mod work_area;
@@ -1,84 +1,517 @@
-use crate::commands::{CommandsState, Vec2f};
+use crate::commands::{CommandsState, Cursor, Rect, Vec2f};
 use crate::components::{
-    Camera2D, Camera2DPositionListener, CameraMovable2D, LayersState, Touch, TouchState,
-    ViewPortSize,
+    Action, Camera2D, Camera2DPositionListener, CameraAnimation2D, CameraBounds2D, CameraMovable2D,
+    CameraRegistry, CursorState, FrameClock, InputLayer, LayersState, TouchState, ViewPortSize,
+    WorkAreaComponent, ACTION_PAN, ACTION_RESET_CAMERA, ACTION_ZOOM, ACTION_ZOOM_IN,
+    ACTION_ZOOM_OUT,
 };
 use crate::debug_services;
+use crate::debug_services::profile::TimedBlockSink;
 use crate::gapi;
 
 use legion::prelude::*;
 
-pub fn move_camera_system() -> Box<dyn Schedulable> {
+pub const MIN_ZOOM: f32 = 0.25;
+pub const MAX_ZOOM: f32 = 8.0;
+/// Ctrl scroll changes zoom `e` times every 500 pixels.
+pub const SCROLL_ZOOM_SPEED: f32 = 1.0 / 500.0;
+pub const ZOOM_STEP: f32 = 1.25;
+
+/// Drags the camera with the centroid of the active pointers, with two or more
+/// pointers the change of their spread zooms around the centroid.
+/// The canvas layer captures the pointer and the cursor is grabbing while the
+/// camera is dragged. With kinetic scrolling the camera keeps gliding after the
+/// release until the friction stops it or a new touch cancels it.
+/// A drag starts only on the camera with the centroid inside of its view port.
+pub fn move_camera_system(sink: TimedBlockSink) -> Box<dyn Schedulable> {
     SystemBuilder::new("move_camera_system")
         .write_resource::<LayersState>()
+        .read_resource::<ViewPortSize>()
+        .read_resource::<CameraRegistry>()
+        .write_resource::<CursorState>()
+        .read_resource::<FrameClock>()
         .with_query(<(Write<Camera2D>, Write<CameraMovable2D>, Read<TouchState>)>::query())
-        .build(|_, mut world, layers_state, query| {
-            debug_services::timed_block!("move_camera_system");
+        .build(
+            move |_,
+                  world,
+                  (layers_state, view_port_size, camera_registry, cursor_state, frame_clock),
+                  query| {
+                let _block = debug_services::timed_block!(sink.clone(), "move_camera_system");
 
-            for (mut camera, mut camera_movable, touch_state) in query.iter(&mut world) {
-                if layers_state.ui_layer_is_hot && !camera_movable.is_hot {
-                    continue;
+                for (mut camera, mut camera_movable, touch_state) in query.iter(world) {
+                    if touch_state.pointers.is_empty() {
+                        if camera_movable.is_hot && camera_movable.kinetic_scrolling.is_enabled {
+                            camera_movable.velocity = touch_state.release_velocity;
+                        }
+
+                        camera_movable.is_hot = false;
+                        layers_state.release(InputLayer::Canvas);
+                        glide_camera(&mut camera, &mut camera_movable, frame_clock);
+                        continue;
+                    }
+
+                    camera_movable.velocity = Vec2f::ZERO;
+
+                    if !layers_state.receives_pointer(InputLayer::Canvas)
+                        || !layers_state.capture(InputLayer::Canvas)
+                    {
+                        camera_movable.is_hot = false;
+                        continue;
+                    }
+
+                    let view_port = camera_registry.view_port_rect(camera.id, view_port_size);
+                    let pointers_count = touch_state.pointers.len();
+                    let centroid = touch_state.pointers_centroid();
+                    let spread = touch_state.pointers_spread(centroid);
+
+                    if !camera_movable.is_hot && !view_port.contains(centroid) {
+                        continue;
+                    }
+
+                    // NOTE: Adding or lifting a pointer moves the centroid, the gesture
+                    // starts over from the current pointers so the camera doesn't jump.
+                    if camera_movable.is_hot && pointers_count == camera_movable.last_pointers_count
+                    {
+                        let zoom = if pointers_count > 1 && camera_movable.last_spread > 0.0 {
+                            camera.zoom * spread / camera_movable.last_spread
+                        }
+                        else {
+                            camera.zoom
+                        };
+
+                        zoom_camera(
+                            &mut camera,
+                            view_port,
+                            camera_movable.last_centroid,
+                            centroid,
+                            zoom,
+                        );
+                    }
+
+                    cursor_state.cursor = Cursor::Grabbing;
+                    camera_movable.is_hot = true;
+                    camera_movable.last_centroid = centroid;
+                    camera_movable.last_spread = spread;
+                    camera_movable.last_pointers_count = pointers_count;
                 }
+            },
+        )
+}
 
-                if camera_movable.is_hot {
-                    layers_state.ui_layer_is_hot = true;
+/// Reacts to the camera actions, pointer drag and pinch are handled by `move_camera_system`.
+/// Only the cameras with `Action.pos` inside of their view ports react.
+pub fn camera_actions_system(sink: TimedBlockSink) -> Box<dyn Schedulable> {
+    SystemBuilder::new("camera_actions_system")
+        .read_resource::<ViewPortSize>()
+        .read_resource::<CameraRegistry>()
+        .with_query(<(Read<Action>,)>::query())
+        .with_query(<(Read<WorkAreaComponent>,)>::query())
+        .with_query(<(Write<Camera2D>,)>::query())
+        .build(
+            move |_,
+                  world,
+                  (view_port_size, camera_registry),
+                  (actions_query, work_area_query, camera_query)| {
+                let _block = debug_services::timed_block!(sink.clone(), "camera_actions_system");
+
+                let actions = actions_query
+                    .iter(world)
+                    .map(|(action,)| (*action).clone())
+                    .collect::<Vec<Action>>();
+
+                if actions.is_empty() {
+                    return;
                 }
 
-                if touch_state.touch == Touch::Start {
-                    layers_state.ui_layer_is_hot = true;
-                    camera_movable.last_pos = camera.pos;
+                let work_area_size = work_area_query
+                    .iter(world)
+                    .next()
+                    .map(|(work_area,)| work_area.size)
+                    .unwrap_or(Vec2f::ZERO);
+
+                for (mut camera,) in camera_query.iter(world) {
+                    let view_port = camera_registry.view_port_rect(camera.id, view_port_size);
+                    let view_port_center = view_port.center();
+
+                    for action in actions.iter() {
+                        if !view_port.contains(action.pos) {
+                            continue;
+                        }
+
+                        match action.name.as_str() {
+                            ACTION_PAN => camera.pos = camera.pos - action.value,
+                            ACTION_ZOOM => {
+                                let zoom =
+                                    camera.zoom * (-action.value.y * SCROLL_ZOOM_SPEED).exp();
+                                zoom_camera(&mut camera, view_port, action.pos, action.pos, zoom);
+                            }
+                            ACTION_ZOOM_IN => {
+                                let zoom = camera.zoom * ZOOM_STEP;
+                                zoom_camera(
+                                    &mut camera,
+                                    view_port,
+                                    view_port_center,
+                                    view_port_center,
+                                    zoom,
+                                );
+                            }
+                            ACTION_ZOOM_OUT => {
+                                let zoom = camera.zoom / ZOOM_STEP;
+                                zoom_camera(
+                                    &mut camera,
+                                    view_port,
+                                    view_port_center,
+                                    view_port_center,
+                                    zoom,
+                                );
+                            }
+                            ACTION_RESET_CAMERA => {
+                                camera.zoom = 1.0;
+                                camera.rotation = 0.0;
+                                camera.pos = work_area_size * -0.5;
+                            }
+                            _ => {}
+                        }
+                    }
                 }
+            },
+        )
+}
+
+/// Has to run after the systems that move the camera. The overscroll is elastic
+/// while the camera is dragged, a released camera springs back into the limits
+/// and stops gliding along the axis that went out of them. Animated cameras are
+/// left to `camera_animation_system`, their targets are already in the limits.
+pub fn camera_bounds_system(sink: TimedBlockSink) -> Box<dyn Schedulable> {
+    SystemBuilder::new("camera_bounds_system")
+        .read_resource::<ViewPortSize>()
+        .read_resource::<CameraRegistry>()
+        .read_resource::<FrameClock>()
+        .with_query(<(Read<WorkAreaComponent>,)>::query())
+        .with_query(<(
+            Write<Camera2D>,
+            Write<CameraMovable2D>,
+            Write<CameraBounds2D>,
+            TryRead<CameraAnimation2D>,
+        )>::query())
+        .build(
+            move |_,
+                  world,
+                  (view_port_size, camera_registry, frame_clock),
+                  (work_area_query, camera_query)| {
+                let _block = debug_services::timed_block!(sink.clone(), "camera_bounds_system");
 
-                if touch_state.touch == Touch::None || touch_state.touch == Touch::End {
-                    camera_movable.is_hot = false;
-                    layers_state.ui_layer_is_hot = false;
-                    break;
+                let work_area_size = match work_area_query.iter(world).next() {
+                    Some((work_area,)) => work_area.size,
+                    None => return,
+                };
+
+                let elapsed = frame_clock.delta().as_secs_f32();
+
+                for (mut camera, mut camera_movable, mut bounds, animation) in
+                    camera_query.iter(world)
+                {
+                    if matches!(animation, Some(animation) if animation.tween.is_some()) {
+                        bounds.last_pos = camera.pos;
+                        continue;
+                    }
+
+                    let view_port = camera_registry.view_port_rect(camera.id, view_port_size);
+                    let (min, max) = bounds.limits(&camera, view_port, work_area_size);
+
+                    if camera_movable.is_hot {
+                        camera.pos = Vec2f::new(
+                            rubber_band(camera.pos.x, bounds.last_pos.x, min.x, max.x, &bounds),
+                            rubber_band(camera.pos.y, bounds.last_pos.y, min.y, max.y, &bounds),
+                        );
+                    }
+                    else {
+                        let target = Vec2f::new(
+                            camera.pos.x.clamp(min.x, max.x),
+                            camera.pos.y.clamp(min.y, max.y),
+                        );
+
+                        if target.x != camera.pos.x {
+                            camera_movable.velocity.x = 0.0;
+                        }
+
+                        if target.y != camera.pos.y {
+                            camera_movable.velocity.y = 0.0;
+                        }
+
+                        camera.pos = spring_back(camera.pos, target, bounds.stiffness, elapsed);
+                    }
+
+                    bounds.last_pos = camera.pos;
                 }
+            },
+        )
+}
 
-                camera_movable.is_hot = true;
-                camera.pos.x = camera_movable.last_pos.x - touch_state.touch_start.x
-                    + touch_state.touch_current.x;
+/// Applies the movement since `last` to the position without the resistance,
+/// then resists the overscroll of the result again.
+fn rubber_band(value: f32, last: f32, min: f32, max: f32, bounds: &CameraBounds2D) -> f32 {
+    let max_overscroll = bounds.max_overscroll;
+    let last_clamped = last.clamp(min, max);
+    let raw = last_clamped + unresist(last - last_clamped, max_overscroll) + (value - last);
+    let raw_clamped = raw.clamp(min, max);
 
-                camera.pos.y = camera_movable.last_pos.y - touch_state.touch_start.y
-                    + touch_state.touch_current.y;
-            }
-        })
+    raw_clamped + resist(raw - raw_clamped, max_overscroll)
 }
 
-pub fn render_touch_system() -> Box<dyn Schedulable> {
+fn resist(overscroll: f32, max_overscroll: f32) -> f32 {
+    let distance = overscroll.abs();
+    overscroll.signum() * distance * max_overscroll / (distance + max_overscroll)
+}
+
+fn unresist(overscroll: f32, max_overscroll: f32) -> f32 {
+    let distance = overscroll.abs().min(max_overscroll * 0.99);
+    overscroll.signum() * distance * max_overscroll / (max_overscroll - distance)
+}
+
+fn spring_back(pos: Vec2f, target: Vec2f, stiffness: f32, elapsed: f32) -> Vec2f {
+    let offset = target - pos;
+
+    if offset.length() < 0.5 {
+        return target;
+    }
+
+    pos + offset * (1.0 - (-stiffness * elapsed).exp())
+}
+
+fn glide_camera(
+    camera: &mut Camera2D,
+    camera_movable: &mut CameraMovable2D,
+    frame_clock: &FrameClock,
+) {
+    if camera_movable.velocity == Vec2f::ZERO {
+        return;
+    }
+
+    let elapsed = frame_clock.delta().as_secs_f32();
+    let kinetic_scrolling = camera_movable.kinetic_scrolling;
+
+    camera.pos += camera_movable.velocity * elapsed;
+    camera_movable.velocity =
+        camera_movable.velocity * (-kinetic_scrolling.friction * elapsed).exp();
+
+    if camera_movable.velocity.length() < kinetic_scrolling.min_velocity {
+        camera_movable.velocity = Vec2f::ZERO;
+    }
+}
+
+/// Sets `zoom` keeping the world point that was under `from` under `to`.
+fn zoom_camera(camera: &mut Camera2D, view_port: Rect, from: Vec2f, to: Vec2f, zoom: f32) {
+    let anchor = camera.screen_to_world(view_port, from);
+
+    camera.zoom = zoom.clamp(MIN_ZOOM, MAX_ZOOM);
+    camera.pos += to - camera.world_to_screen(view_port, anchor);
+}
+
+pub fn render_touch_system(sink: TimedBlockSink) -> Box<dyn Schedulable> {
     SystemBuilder::new("render_touch_system")
         .write_resource::<CommandsState>()
-        .read_resource::<ViewPortSize>()
+        .read_resource::<CameraRegistry>()
         .with_query(<(
             Read<TouchState>,
             Read<CameraMovable2D>,
             Read<Camera2DPositionListener>,
         )>::query())
-        .build(|_, mut world, (commands_state, _), query| {
-            gapi::set_camera(commands_state, gapi::CAMERA_ORTHO);
+        .build(move |_, world, (commands_state, camera_registry), query| {
+            let _block = debug_services::timed_block!(sink.clone(), "render_touch_system");
 
-            gapi::push_color_shader(commands_state);
-            gapi::push_color_rgb(commands_state, 1.0, 0.0, 0.0);
-            gapi::set_color_uniform(commands_state);
-
-            for (touch, camera_movable, camera_listener) in query.iter(&mut world) {
+            for (touch, camera_movable, camera_listener) in query.iter(world) {
                 if !camera_movable.is_hot {
-                    break;
+                    continue;
                 }
 
-                let pos = Vec2f {
-                    x: -camera_listener.pos.x - 16.0 + touch.touch_current.x,
-                    // y: view_port_size.height as f32
-                    //     - camera_listener.pos.y
-                    //     - 16.0
-                    //     - touch.touch_current.y,
-                    y: -camera_listener.pos.y - 16.0 + touch.touch_current.y,
+                // 32 screen pixels around the pointer.
+                let size = match camera_registry.screen_length_to_world(camera_listener.id, 32.0) {
+                    Some(size) => Vec2f::new(size, size),
+                    None => continue,
                 };
-                let size = Vec2f { x: 32.0, y: 32.0 };
 
-                gapi::push_quad_lines(commands_state, pos, size);
-            }
+                gapi::set_camera(commands_state, camera_listener.id);
+
+                gapi::push_color_shader(commands_state);
+                gapi::push_color_rgb(commands_state, 1.0, 0.0, 0.0);
+                gapi::set_color_uniform(commands_state);
+
+                for pointer in touch.pointers.iter() {
+                    if let Some(pos) =
+                        camera_registry.screen_to_world(camera_listener.id, pointer.current)
+                    {
+                        gapi::push_quad_lines(commands_state, pos - size / 2.0, size);
+                    }
+                }
 
-            gapi::draw_lines(commands_state);
+                gapi::draw_lines(commands_state);
+            }
         })
 }
+
+#[cfg(test)]
+mod tests {
+    use legion::prelude::*;
+
+    use crate::commands::{ExecutionCommandType, Rect, Vec2f};
+    use crate::components::{Camera2D, KEY_MODIFIER_CTRL};
+    use crate::errors::StatusCode;
+    use crate::test_utils::TestWorld;
+
+    #[test]
+    fn pinch_zoom() {
+        let mut world = TestWorld::with_view_port(640, 480);
+
+        crate::push_on_touch_start_request_command(world.state(), 0, 0, Vec2f::new(400.0, 300.0));
+        crate::push_on_touch_start_request_command(world.state(), 0, 1, Vec2f::new(500.0, 300.0));
+        assert_eq!(StatusCode::Ok, unsafe { crate::step(world.handle) });
+        assert_eq!((Vec2f::new(-320.0, -240.0), 1.0), world.camera_transform());
+
+        // The world point under the centroid stays under the new centroid.
+        crate::push_on_touch_move_request_command(world.state(), 0, 1, Vec2f::new(600.0, 300.0));
+        assert_eq!(StatusCode::Ok, unsafe { crate::step(world.handle) });
+        assert_eq!((Vec2f::new(-720.0, -540.0), 2.0), world.camera_transform());
+
+        crate::push_on_touch_end_request_command(world.state(), 0, 0, Vec2f::new(400.0, 300.0));
+        assert_eq!(StatusCode::Ok, unsafe { crate::step(world.handle) });
+        assert_eq!((Vec2f::new(-720.0, -540.0), 2.0), world.camera_transform());
+
+        crate::push_on_touch_move_request_command(world.state(), 0, 1, Vec2f::new(610.0, 320.0));
+        assert_eq!(StatusCode::Ok, unsafe { crate::step(world.handle) });
+        assert_eq!((Vec2f::new(-710.0, -520.0), 2.0), world.camera_transform());
+    }
+
+    #[test]
+    fn scroll() {
+        let mut world = TestWorld::with_view_port(640, 480);
+
+        crate::push_on_touch_move_request_command(world.state(), 0, 0, Vec2f::new(400.0, 300.0));
+        crate::push_on_scroll_request_command(world.state(), Vec2f::new(10.0, 20.0), 0);
+        world.step();
+        assert_eq!((Vec2f::new(-330.0, -260.0), 1.0), world.camera_transform());
+
+        // The world point under the cursor stays in place.
+        let delta = Vec2f::new(0.0, -500.0 * 2.0_f32.ln());
+        crate::push_on_scroll_request_command(world.state(), delta, KEY_MODIFIER_CTRL);
+        assert_eq!(StatusCode::Ok, unsafe { crate::step(world.handle) });
+
+        let (pos, zoom) = world.camera_transform();
+        assert!((zoom - 2.0).abs() < 1e-4);
+        assert!((pos - Vec2f::new(-740.0, -580.0)).length() < 1e-2);
+    }
+
+    #[test]
+    fn kinetic_panning() {
+        let mut world = TestWorld::with_view_port(640, 480);
+
+        crate::push_on_touch_start_request_command(world.state(), 0, 0, Vec2f::new(400.0, 300.0));
+        world.step();
+        crate::push_on_touch_move_request_command(world.state(), 10, 0, Vec2f::new(410.0, 300.0));
+        world.step();
+        crate::push_on_touch_end_request_command(world.state(), 20, 0, Vec2f::new(410.0, 300.0));
+        world.step();
+        assert_eq!((Vec2f::new(-310.0, -240.0), 1.0), world.camera_transform());
+
+        // The camera keeps the release velocity of 1000 pixels per second.
+        world.set_frame_time(100);
+        world.step();
+        let (pos, _) = world.camera_transform();
+        assert!((pos - Vec2f::new(-210.0, -240.0)).length() < 1e-2);
+
+        world.set_frame_time(200);
+        world.step();
+        let (pos, _) = world.camera_transform();
+        assert!(pos.x > -210.0 && pos.x < -110.0);
+
+        // A new touch stops the glide.
+        crate::push_on_touch_start_request_command(world.state(), 0, 0, Vec2f::new(400.0, 300.0));
+        world.set_frame_time(300);
+        world.step();
+        crate::push_on_touch_end_request_command(world.state(), 0, 0, Vec2f::new(400.0, 300.0));
+        world.step();
+        world.set_frame_time(400);
+        world.step();
+        assert_eq!(pos, world.camera_transform().0);
+    }
+
+    #[test]
+    fn camera_bounds() {
+        let mut world = TestWorld::with_view_port(640, 480);
+
+        crate::push_on_touch_start_request_command(world.state(), 0, 0, Vec2f::new(100.0, 240.0));
+        world.step();
+
+        let mut touch_move = |time_offset: i32, x: f32| {
+            crate::push_on_touch_move_request_command(
+                world.state(),
+                time_offset,
+                0,
+                Vec2f::new(x, 240.0),
+            );
+            world.step();
+            world.camera_transform().0.x
+        };
+
+        // The camera can move up to 256 keeping 64 pixels of the work area visible.
+        assert_eq!(180.0, touch_move(10, 600.0));
+
+        let overscroll = touch_move(20, 1100.0) - 256.0;
+        assert!(overscroll > 0.0 && overscroll < 120.0);
+
+        // Dragging back undoes the resistance.
+        assert!((touch_move(30, 600.0) - 180.0).abs() < 1e-2);
+        assert!(touch_move(40, 1100.0) > 256.0);
+
+        crate::push_on_touch_end_request_command(world.state(), 1000, 0, Vec2f::new(1100.0, 240.0));
+        world.step();
+        world.set_frame_time(1000);
+        world.step();
+        assert!((world.camera_transform().0.x - 256.0).abs() < 1e-2);
+    }
+
+    #[test]
+    fn camera_rotation() {
+        let mut world = TestWorld::with_view_port(640, 480);
+        let view_port = Rect::new(Vec2f::ZERO, Vec2f::new(640.0, 480.0));
+        let cursor = Vec2f::new(400.0, 300.0);
+
+        world.step();
+
+        let anchor = {
+            let state = world.state();
+            let query = <(Write<Camera2D>,)>::query();
+            let (mut camera,) = query.iter(&mut state.world).next().unwrap();
+            camera.rotation = std::f32::consts::FRAC_PI_2;
+            camera.screen_to_world(view_port, cursor)
+        };
+
+        // The world point under the cursor stays in place on the rotated camera.
+        crate::push_on_touch_move_request_command(world.state(), 0, 0, cursor);
+        let delta = Vec2f::new(0.0, -500.0 * 2.0_f32.ln());
+        crate::push_on_scroll_request_command(world.state(), delta, KEY_MODIFIER_CTRL);
+        let exec_commands = world.step();
+
+        {
+            let state = world.state();
+            let query = <(Read<Camera2D>,)>::query();
+            let (camera,) = query.iter(&mut state.world).next().unwrap();
+
+            assert!((camera.zoom - 2.0).abs() < 1e-4);
+            assert!((camera.world_to_screen(view_port, anchor) - cursor).length() < 1e-2);
+        }
+
+        // The transform of the previous step, with the rotation applied.
+        let transform = exec_commands
+            .windows(5)
+            .find(|commands| {
+                commands[4].command_type == ExecutionCommandType::UpdateCameraTransform
+            })
+            .unwrap();
+
+        assert_eq!(crate::gapi::CAMERA_ORTHO as i32, transform[0].data.int32);
+        assert!((transform[1].data.vec2f - Vec2f::new(0.0, 1.0)).length() < 1e-6);
+        assert!((transform[2].data.vec2f - Vec2f::new(-1.0, 0.0)).length() < 1e-6);
+        assert_eq!(Vec2f::ZERO, transform[3].data.vec2f);
+    }
+}
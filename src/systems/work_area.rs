@@ -1,25 +1,37 @@
-use crate::commands::{CommandsState, Vec2f};
-use crate::components::WorkAreaComponent;
+use crate::commands::{CommandsState, Rect, Vec2f};
+use crate::components::{Camera2DPositionListener, CameraRegistry, WorkAreaComponent};
 use crate::debug_services;
+use crate::debug_services::profile::TimedBlockSink;
 use crate::gapi;
 
 use legion::prelude::*;
 
-pub fn render_work_area_system() -> Box<dyn Schedulable> {
+pub fn render_work_area_system(sink: TimedBlockSink) -> Box<dyn Schedulable> {
     SystemBuilder::new("work_area_system")
         .write_resource::<CommandsState>()
-        .with_query(<(Read<WorkAreaComponent>,)>::query())
-        .build(|_, mut world, commands_state, query| {
-            debug_services::timed_block!("work_area_system");
+        .read_resource::<CameraRegistry>()
+        .with_query(<(Read<WorkAreaComponent>, Read<Camera2DPositionListener>)>::query())
+        .build(move |_, world, (commands_state, camera_registry), query| {
+            let _block = debug_services::timed_block!(sink.clone(), "work_area_system");
+
+            for (work_area, camera) in query.iter(world) {
+                let rect = Rect::new(Vec2f::ZERO, work_area.size);
+
+                let is_visible = camera_registry
+                    .visible_world_rect(camera.id)
+                    .is_some_and(|visible| rect.intersects(&visible));
+
+                if !is_visible {
+                    continue;
+                }
 
-            for (work_area,) in query.iter(&mut world) {
                 gapi::push_color_shader(commands_state);
                 gapi::push_color(commands_state, work_area.color);
                 gapi::set_color_uniform(commands_state);
-                gapi::push_quad_lines(commands_state, Vec2f::ZERO, work_area.size);
-            }
+                gapi::push_quad_lines(commands_state, rect.pos, rect.size);
 
-            gapi::set_camera(commands_state, gapi::CAMERA_ORTHO);
-            gapi::draw_lines(commands_state);
+                gapi::set_camera(commands_state, camera.id);
+                gapi::draw_lines(commands_state);
+            }
         })
 }
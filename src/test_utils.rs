@@ -0,0 +1,122 @@
+use std::ptr;
+use std::thread;
+use std::time::Duration;
+
+use legion::prelude::*;
+
+use crate::commands::{CommandsState, ExecutionCommand, Vec2f, Vec2i};
+use crate::components::{Camera2D, FrameClock};
+use crate::errors::StatusCode;
+use crate::{ApplicationState, RawBuffer};
+
+/// World created through the entry points the way the host does it,
+/// destroyed when dropped. The handle stays valid until then, the state is
+/// borrowed through `&mut self` so the tests can't alias it.
+pub struct TestWorld {
+    pub handle: *mut ApplicationState,
+}
+
+impl TestWorld {
+    pub fn new() -> TestWorld {
+        let mut handle = ptr::null_mut();
+        assert_eq!(StatusCode::Ok, unsafe { crate::init_world(&mut handle) });
+
+        TestWorld { handle }
+    }
+
+    /// The size is applied by the next step.
+    pub fn with_view_port(width: i32, height: i32) -> TestWorld {
+        let mut world = TestWorld::new();
+        crate::push_set_view_port_size_request_command(world.state(), Vec2i::new(width, height));
+
+        world
+    }
+
+    pub fn state(&mut self) -> &mut ApplicationState {
+        unsafe { crate::get_application_state(self.handle) }.unwrap()
+    }
+
+    /// Runs the step and ends the frame, returns the execution commands of the frame.
+    pub fn step(&mut self) -> Vec<ExecutionCommand> {
+        assert_eq!(StatusCode::Ok, unsafe { crate::step(self.handle) });
+
+        let exec_commands = {
+            let state = self.state();
+            let commands_state = state.world.resources.get::<CommandsState>().unwrap();
+            commands_state.exec_commands.clone()
+        };
+
+        self.end_frame();
+        exec_commands
+    }
+
+    /// Runs the step and the render passes, `inspect` receives the world
+    /// before the frame ends.
+    pub fn render<F>(&mut self, inspect: F)
+    where
+        F: FnOnce(&mut ApplicationState),
+    {
+        unsafe {
+            assert_eq!(StatusCode::Ok, crate::step(self.handle));
+            assert_eq!(StatusCode::Ok, crate::render_pass1(self.handle));
+            assert_eq!(StatusCode::Ok, crate::render_pass2(self.handle));
+        }
+
+        inspect(self.state());
+
+        self.end_frame();
+    }
+
+    /// The whole frame the way the host runs it.
+    pub fn run_frame(&mut self) {
+        assert_eq!(StatusCode::Ok, unsafe { crate::frame_start(self.handle) });
+        self.render(|_| ());
+    }
+
+    pub fn end_frame(&mut self) {
+        unsafe {
+            assert_eq!(StatusCode::Ok, crate::frame_end(self.handle));
+            assert_eq!(StatusCode::Ok, crate::flush(self.handle));
+        }
+    }
+
+    pub fn execute(&mut self, command: &str) -> StatusCode {
+        unsafe { crate::c_execute_command(self.handle, RawBuffer::from_string(command)) }
+    }
+
+    pub fn set_frame_time(&mut self, millis: u64) {
+        let state = self.state();
+        let mut frame_clock = state.world.resources.get_mut::<FrameClock>().unwrap();
+        frame_clock.set_time(Duration::from_millis(millis));
+    }
+
+    /// Position and zoom of the first camera.
+    pub fn camera_transform(&mut self) -> (Vec2f, f32) {
+        let state = self.state();
+        let query = <(Read<Camera2D>,)>::query();
+        let (camera,) = query.iter(&mut state.world).next().unwrap();
+
+        (camera.pos, camera.zoom)
+    }
+
+    pub fn camera_pos(&mut self, id: usize) -> Vec2f {
+        let state = self.state();
+        let query = <(Read<Camera2D>,)>::query();
+        let mut cameras = query.iter(&mut state.world);
+        cameras.find(|(camera,)| camera.id == id).unwrap().0.pos
+    }
+}
+
+impl Drop for TestWorld {
+    fn drop(&mut self) {
+        let status = unsafe { crate::destroy_world(self.handle) };
+
+        if !thread::panicking() {
+            assert_eq!(StatusCode::Ok, status);
+        }
+    }
+}
+
+pub fn last_error() -> String {
+    crate::get_last_error().data_to_string().unwrap()
+}